@@ -0,0 +1,166 @@
+use petrovich_core::coverage::{CorpusEntry, Coverage, RuleUsage, Unmatched};
+use petrovich_core::gender::Gender;
+use petrovich_core::lint::Lint;
+use petrovich_core::name::NamePart;
+use petrovich_core::number::Number;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::error::Error;
+
+//Formats of a corpus file
+pub const PLAIN: &str = "plain";
+pub const EVAL: &str = "eval";
+
+#[derive(Serialize)]
+struct CoverageReport {
+    words: usize,
+    rules: Vec<RuleReport>,
+    unused: Vec<RuleReport>,
+    unmatched: Vec<UnmatchedReport>,
+}
+
+#[derive(Serialize)]
+struct RuleReport {
+    section: String,
+    index: usize,
+    line: Option<usize>,
+    gender: String,
+    test: Vec<String>,
+    count: usize,
+}
+
+#[derive(Serialize)]
+struct UnmatchedReport {
+    word: String,
+    name: String,
+    part: String,
+    gender: String,
+    number: String,
+}
+
+//One problem per line followed by their number
+pub fn lint_text(lints: &[Lint]) -> String {
+    let mut result: String = lints.iter().map(|lint| format!("{}\n", lint)).collect();
+    match lints.len() {
+        0 => result.push_str("No problems found\n"),
+        1 => result.push_str("1 problem found\n"),
+        count => result.push_str(&format!("{} problems found\n", count)),
+    }
+    result
+}
+
+//A name per line, empty lines are skipped
+pub fn read_plain(content: &str, part: NamePart, gender: Gender) -> Vec<CorpusEntry> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|name| CorpusEntry {
+            part,
+            name: name.to_string(),
+            gender,
+            number: Number::Singular,
+        })
+        .collect()
+}
+
+//petrovich-eval TSV: a header, then "Иванов	Иванову	мр,ед,дт".
+//A name is listed once per case, so repeated names are counted once.
+pub fn read_eval(content: &str, part: NamePart) -> Result<Vec<CorpusEntry>, Box<dyn Error>> {
+    let mut result = Vec::new();
+    let mut seen = HashSet::new();
+    for (index, line) in content.lines().enumerate().skip(1) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let fields: Vec<&str> = line.split('\t').collect();
+        let grammemes: Vec<&str> = match fields[..] {
+            [_, _, grammemes] => grammemes.split(',').collect(),
+            _ => return Err(format!("Line {}: expected 3 columns", index + 1).into()),
+        };
+        let entry = CorpusEntry {
+            part,
+            name: fields[0].to_string(),
+            gender: match grammemes[0] {
+                "мр" => Gender::Male,
+                "жр" => Gender::Female,
+                _ => Gender::Androgynous,
+            },
+            number: match grammemes.get(1) {
+                Some(&"мн") => Number::Plural,
+                _ => Number::Singular,
+            },
+        };
+        if seen.insert((
+            entry.name.clone(),
+            entry.gender.value(),
+            entry.number.value(),
+        )) {
+            result.push(entry);
+        }
+    }
+    Ok(result)
+}
+
+//"2	lastname.suffixes[0] (line 8)	ов, ев" per rule followed by the totals and the unmatched words
+pub fn coverage_text(coverage: &Coverage) -> String {
+    let mut result = String::new();
+    for usage in &coverage.rules {
+        result.push_str(&format!(
+            "{}\t{}\t{}\n",
+            usage.count,
+            usage.rule.location(),
+            usage.rule.test.join(", ")
+        ));
+    }
+    result.push_str(&format!(
+        "\nUnused rules: {} of {}\n",
+        coverage.unused().count(),
+        coverage.rules.len()
+    ));
+    result.push_str(&format!(
+        "Words without a rule: {} of {}\n",
+        coverage.unmatched.len(),
+        coverage.words
+    ));
+    for unmatched in &coverage.unmatched {
+        let entry = &unmatched.entry;
+        result.push_str(&format!(
+            "{} in {} ({}, {}, {})\n",
+            unmatched.word, entry.name, entry.part, entry.gender, entry.number
+        ));
+    }
+    result
+}
+
+pub fn coverage_json(coverage: &Coverage) -> Result<String, Box<dyn Error>> {
+    let report = CoverageReport {
+        words: coverage.words,
+        rules: coverage.rules.iter().map(rule_report).collect(),
+        unused: coverage.unused().map(rule_report).collect(),
+        unmatched: coverage.unmatched.iter().map(unmatched_report).collect(),
+    };
+    Ok(serde_json::to_string_pretty(&report)?)
+}
+
+fn rule_report(usage: &RuleUsage) -> RuleReport {
+    RuleReport {
+        section: usage.rule.section.clone(),
+        index: usage.rule.index,
+        line: usage.rule.line,
+        gender: usage.rule.gender.to_string(),
+        test: usage.rule.test.clone(),
+        count: usage.count,
+    }
+}
+
+fn unmatched_report(unmatched: &Unmatched) -> UnmatchedReport {
+    let entry = &unmatched.entry;
+    UnmatchedReport {
+        word: unmatched.word.clone(),
+        name: entry.name.clone(),
+        part: entry.part.to_string(),
+        gender: entry.gender.to_string(),
+        number: entry.number.to_string(),
+    }
+}
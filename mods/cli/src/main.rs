@@ -0,0 +1,118 @@
+use clap::{Parser, Subcommand};
+use petrovich_core::gender::Gender;
+use petrovich_core::name::NamePart;
+use petrovich_core::Petrovich;
+use std::error::Error;
+use std::fs;
+use std::process::ExitCode;
+
+#[derive(Parser)]
+#[command(about = "Tools for petrovich rules files")]
+struct Args {
+    #[arg(
+        long,
+        global = true,
+        help = "Rules file, the embedded rules are used if the tool is built with them"
+    )]
+    rules: Option<String>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    #[command(
+        about = "Reports shadowed, duplicate and conflicting rules, wrong mods and unmatchable tests"
+    )]
+    Lint,
+    #[command(about = "Counts the words of a name corpus matched by every rule")]
+    Coverage {
+        #[arg(help = "Corpus file, a name per line or a petrovich-eval TSV file")]
+        corpus: String,
+        #[arg(
+            long,
+            help = "Name part of the corpus: lastname, firstname or middlename"
+        )]
+        part: String,
+        #[arg(long, default_value = petrovich_cli::PLAIN, help = "Corpus format: plain or eval")]
+        format: String,
+        #[arg(
+            long,
+            default_value = "androgynous",
+            help = "Gender of the names of a plain corpus: male, female or androgynous"
+        )]
+        gender: String,
+        #[arg(long, help = "Prints the report as JSON")]
+        json: bool,
+    },
+}
+
+fn load_petrovich(rules: Option<&str>) -> Result<Petrovich, Box<dyn Error>> {
+    match rules {
+        Some(rules) => Petrovich::new(rules),
+        #[cfg(feature = "embedded-rules")]
+        None => Petrovich::embedded(),
+        #[cfg(not(feature = "embedded-rules"))]
+        None => Err("No rules file given, use --rules".into()),
+    }
+}
+
+fn coverage(
+    petrovich: &Petrovich,
+    corpus: &str,
+    part: &str,
+    format: &str,
+    gender: &str,
+    json: bool,
+) -> Result<(), Box<dyn Error>> {
+    let part = NamePart::of(part).ok_or(format!("Unknown name part '{}'", part))?;
+    let gender = Gender::of(gender).ok_or(format!("Unknown gender '{}'", gender))?;
+    let content = fs::read_to_string(corpus)?;
+    let corpus = match format {
+        petrovich_cli::PLAIN => petrovich_cli::read_plain(&content, part, gender),
+        petrovich_cli::EVAL => petrovich_cli::read_eval(&content, part)?,
+        _ => return Err(format!("Unknown corpus format '{}'", format).into()),
+    };
+    let coverage = petrovich.coverage(&corpus);
+    if json {
+        println!("{}", petrovich_cli::coverage_json(&coverage)?);
+    } else {
+        print!("{}", petrovich_cli::coverage_text(&coverage));
+    }
+    Ok(())
+}
+
+fn run(args: Args) -> Result<ExitCode, Box<dyn Error>> {
+    let petrovich = load_petrovich(args.rules.as_deref())?;
+    match args.command {
+        Command::Lint => {
+            let lints = petrovich.lint();
+            print!("{}", petrovich_cli::lint_text(&lints));
+            if lints.is_empty() {
+                Ok(ExitCode::SUCCESS)
+            } else {
+                Ok(ExitCode::FAILURE)
+            }
+        }
+        Command::Coverage {
+            corpus,
+            part,
+            format,
+            gender,
+            json,
+        } => {
+            coverage(&petrovich, &corpus, &part, &format, &gender, json)?;
+            Ok(ExitCode::SUCCESS)
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    match run(Args::parse()) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
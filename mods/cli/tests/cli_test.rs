@@ -0,0 +1,153 @@
+use serde_json::{json, Value};
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+const RULES: &str = "
+lastname:
+  suffixes:
+    - gender: androgynous
+      test: [ов]
+      mods: [а, у, а, ым, е]
+    - gender: male
+      test: [ов, ков]
+      mods: [а, у, а, ым]
+";
+
+//Writes a rules or corpus file of the test
+fn temp_file(name: &str, content: &str) -> PathBuf {
+    let path =
+        std::env::temp_dir().join(format!("petrovich-cli-{}-{}.yml", name, std::process::id()));
+    fs::write(&path, content).unwrap();
+    path
+}
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_petrovich-cli"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn lint_test() {
+    let path = temp_file("lint", RULES);
+    let output = run(&["lint", "--rules", path.to_str().unwrap()]);
+    fs::remove_file(&path).unwrap();
+    let expected = "\
+lastname.suffixes[1] (line 7): mods-length: 4 mods
+lastname.suffixes[1] (line 7): duplicate: test 'ов' is already in lastname.suffixes[0] (line 4)
+lastname.suffixes[1] (line 7): shadowed: test 'ков' is matched by 'ов' of lastname.suffixes[0] (line 4)
+3 problems found
+";
+    assert_eq!(expected, String::from_utf8(output.stdout).unwrap());
+    assert_eq!(Some(1), output.status.code());
+}
+
+#[test]
+fn clean_lint_test() {
+    let path = temp_file("clean", "{}");
+    let output = run(&["lint", "--rules", path.to_str().unwrap()]);
+    fs::remove_file(&path).unwrap();
+    assert_eq!(
+        "No problems found\n",
+        String::from_utf8(output.stdout).unwrap()
+    );
+    assert!(output.status.success());
+}
+
+#[test]
+fn missing_rules_test() {
+    let output = run(&["lint", "--rules", "missing.yml"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn coverage_test() {
+    let rules = temp_file("coverage", RULES);
+    let corpus = temp_file("coverage-corpus", "Иванов\n\nПетров\nБонч-Бруевич\n");
+    let output = run(&[
+        "coverage",
+        corpus.to_str().unwrap(),
+        "--part",
+        "lastname",
+        "--gender",
+        "male",
+        "--rules",
+        rules.to_str().unwrap(),
+    ]);
+    fs::remove_file(&rules).unwrap();
+    fs::remove_file(&corpus).unwrap();
+    let text = String::from_utf8(output.stdout).unwrap();
+    assert!(text.starts_with(
+        "\
+2\tlastname.suffixes[0] (line 4)\tов
+0\tlastname.suffixes[1] (line 7)\tов, ков
+"
+    ));
+    assert!(text.contains("Words without a rule: 2 of 4\n"));
+    assert!(text.ends_with(
+        "\
+бонч in Бонч-Бруевич (lastname, male, singular)
+бруевич in Бонч-Бруевич (lastname, male, singular)
+"
+    ));
+    assert!(output.status.success());
+}
+
+#[test]
+fn coverage_json_test() {
+    let rules = temp_file("coverage-json", RULES);
+    let corpus = temp_file(
+        "coverage-json-corpus",
+        "word\tinflected\tgrammemes\nПетров\tПетрова\tмр,ед,рд\nПетров\tПетрову\tмр,ед,дт\n",
+    );
+    let output = run(&[
+        "coverage",
+        corpus.to_str().unwrap(),
+        "--part",
+        "lastname",
+        "--format",
+        "eval",
+        "--json",
+        "--rules",
+        rules.to_str().unwrap(),
+    ]);
+    fs::remove_file(&rules).unwrap();
+    fs::remove_file(&corpus).unwrap();
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json!(1), report["words"]);
+    let expected = json!({
+        "section": "lastname.suffixes",
+        "index": 0,
+        "line": 4,
+        "gender": "androgynous",
+        "test": ["ов"],
+        "count": 1
+    });
+    assert_eq!(expected, report["rules"][0]);
+    assert_eq!(json!(7), report["unused"][0]["line"]);
+    assert_eq!(json!([]), report["unmatched"]);
+}
+
+#[test]
+fn wrong_corpus_test() {
+    let rules = temp_file("wrong", RULES);
+    let corpus = temp_file("wrong-corpus", "word\tinflected\tgrammemes\nПетров\n");
+    let (rules_path, path) = (rules.to_str().unwrap(), corpus.to_str().unwrap());
+    let output = run(&[
+        "coverage", path, "--part", "lastname", "--format", "eval", "--rules", rules_path,
+    ]);
+    assert_eq!(
+        "Error: Line 2: expected 3 columns\n",
+        String::from_utf8(output.stderr).unwrap()
+    );
+    let output = run(&["coverage", path, "--part", "surname", "--rules", rules_path]);
+    assert_eq!(
+        "Error: Unknown name part 'surname'\n",
+        String::from_utf8(output.stderr).unwrap()
+    );
+    assert!(!output.status.success());
+    fs::remove_file(&rules).unwrap();
+    fs::remove_file(&corpus).unwrap();
+}
@@ -0,0 +1,83 @@
+#[path = "../tests/counting/mod.rs"]
+mod counting;
+
+use counting::allocations;
+use criterion::{criterion_group, criterion_main, Criterion};
+use petrovich_core::case::Case;
+use petrovich_core::gender::Gender;
+use petrovich_core::name::NamePart;
+use petrovich_core::Petrovich;
+use std::hint::black_box;
+
+const RULES_FILE: &str = "petrovich-rules/rules.yml";
+const NAMES: [&str; 6] = [
+    "Иванов",
+    "Петрова",
+    "Бонч-Бруевич",
+    "Достоевский",
+    "Черных",
+    "Римский-Корсаков",
+];
+
+fn load_petrovich() -> Petrovich {
+    Petrovich::new(RULES_FILE).unwrap_or_else(|error| panic!("Problem reading rules: {:?}", error))
+}
+
+fn inflect_benchmark(c: &mut Criterion) {
+    let petrovich = load_petrovich();
+    let part = &NamePart::LastName;
+    let gender = &Gender::Male;
+    let case = &Case::Dative;
+    let mut out = String::with_capacity(64);
+
+    let inflect = allocations(|| {
+        for name in NAMES {
+            black_box(petrovich.inflect(part, name, gender, case));
+        }
+    });
+    let inflect_into = allocations(|| {
+        for name in NAMES {
+            out.clear();
+            petrovich.inflect_into(part, name, gender, case, &mut out);
+        }
+    });
+    let inflect_cow = allocations(|| {
+        for name in NAMES {
+            black_box(petrovich.inflect_cow(part, name, gender, &Case::Nominative));
+        }
+    });
+    println!(
+        "allocations for {} names: inflect {}, inflect_into {}, inflect_cow (nominative) {}",
+        NAMES.len(),
+        inflect,
+        inflect_into,
+        inflect_cow
+    );
+
+    c.bench_function("inflect", |b| {
+        b.iter(|| {
+            for name in NAMES {
+                black_box(petrovich.inflect(part, black_box(name), gender, case));
+            }
+        })
+    });
+    c.bench_function("inflect_into", |b| {
+        b.iter(|| {
+            for name in NAMES {
+                out.clear();
+                petrovich.inflect_into(part, black_box(name), gender, case, &mut out);
+                black_box(&out);
+            }
+        })
+    });
+    c.bench_function("inflect_cow", |b| {
+        b.iter(|| {
+            for name in NAMES {
+                black_box(petrovich.inflect_cow(part, black_box(name), gender, case));
+            }
+        })
+    });
+}
+
+criterion_group!(benches, inflect_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,64 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use petrovich_core::case::Case;
+use petrovich_core::gender::Gender;
+use petrovich_core::name::{FullName, NamePart};
+use petrovich_core::normalize::Normalization;
+use petrovich_core::Petrovich;
+use std::sync::OnceLock;
+
+const RULES: &str = include_str!("../rules.yml");
+
+fn petrovich(fold_yo: bool) -> &'static Petrovich {
+    static PETROVICH: OnceLock<[Petrovich; 2]> = OnceLock::new();
+    let petrovich = PETROVICH.get_or_init(|| {
+        [false, true].map(|fold_yo| {
+            let mut petrovich: Petrovich = RULES.parse().unwrap();
+            petrovich.set_normalization(Normalization {
+                fold_yo,
+                ..Normalization::default()
+            });
+            petrovich
+        })
+    });
+    &petrovich[fold_yo as usize]
+}
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&flags, name)) = data.split_first() else {
+        return;
+    };
+    let Ok(name) = std::str::from_utf8(name) else {
+        return;
+    };
+    let petrovich = petrovich(flags & 1 == 1);
+    let gender = match (flags >> 1) & 3 {
+        0 => Gender::Male,
+        1 => Gender::Female,
+        _ => Gender::Androgynous,
+    };
+    let mut out = String::new();
+    for case in [
+        Case::Nominative,
+        Case::Genitive,
+        Case::Dative,
+        Case::Accusative,
+        Case::Instrumental,
+        Case::Prepositional,
+    ] {
+        for part in NamePart::values() {
+            let expected = petrovich.inflect(&part, name, &gender, &case);
+            out.clear();
+            petrovich.inflect_into(&part, name, &gender, &case, &mut out);
+            assert_eq!(expected, out);
+            assert_eq!(expected, petrovich.inflect_cow(&part, name, &gender, &case));
+            let detailed = petrovich.inflect_detailed(&part, name, &gender, &case);
+            assert_eq!(expected, detailed.value);
+        }
+        let full_name = FullName::new(name, name, name);
+        petrovich.full_name(&full_name, &gender, &case);
+    }
+    petrovich.first_name_form(name, &gender, "vocative");
+    let _ = petrovich.parse_name(name);
+});
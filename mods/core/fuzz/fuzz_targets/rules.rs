@@ -0,0 +1,17 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use petrovich_core::case::Case;
+use petrovich_core::gender::Gender;
+use petrovich_core::Petrovich;
+
+// Rules which pass loading must never make inflection panic
+fuzz_target!(|content: &str| {
+    if let Ok(petrovich) = content.parse::<Petrovich>() {
+        for name in ["", "Иванов", "Лев", "Бонч-Бруевич", "ь"] {
+            petrovich.last_name(name, &Gender::Male, &Case::Genitive);
+            petrovich.first_name(name, &Gender::Female, &Case::Instrumental);
+            petrovich.middle_name_form(name, &Gender::Androgynous, "vocative");
+        }
+    }
+});
@@ -8,8 +8,12 @@ const ACCUSATIVE: &str = "accusative";
 const INSTRUMENTAL: &str = "instrumental";
 const PREPOSITIONAL: &str = "prepositional";
 
+//Names of additional forms with built-in rules
+//звательный
+pub const VOCATIVE: &str = "vocative";
+
 //Grammar case constants
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone, Copy)]
 pub enum Case {
     //именительный
     Nominative,
@@ -36,6 +40,18 @@ impl Case {
             Case::Prepositional => PREPOSITIONAL,
         }
     }
+
+    pub fn of(case: &str) -> Option<Case> {
+        match case {
+            NOMINATIVE => Some(Case::Nominative),
+            GENITIVE => Some(Case::Genitive),
+            DATIVE => Some(Case::Dative),
+            ACCUSATIVE => Some(Case::Accusative),
+            INSTRUMENTAL => Some(Case::Instrumental),
+            PREPOSITIONAL => Some(Case::Prepositional),
+            _ => None,
+        }
+    }
 }
 
 impl Display for Case {
@@ -0,0 +1,177 @@
+use crate::gender::Gender;
+use crate::inflection::{Inflection, MatchKind, RuleInfo};
+use crate::name::NamePart;
+use crate::number::Number;
+use crate::rules::Rules;
+use std::collections::HashMap;
+
+//A name of a corpus with its grammemes
+#[derive(PartialEq, Debug, Clone)]
+pub struct CorpusEntry {
+    pub part: NamePart,
+    pub name: String,
+    pub gender: Gender,
+    pub number: Number,
+}
+
+//Number of corpus words matched by a rule
+#[derive(PartialEq, Debug, Clone)]
+pub struct RuleUsage {
+    pub rule: RuleInfo,
+    pub count: usize,
+}
+
+//A word of a (possibly hyphenated) name no rule matched
+#[derive(PartialEq, Debug, Clone)]
+pub struct Unmatched {
+    pub word: String,
+    pub entry: CorpusEntry,
+}
+
+//Which rules of the rules file match the words of a corpus
+#[derive(PartialEq, Debug, Clone)]
+pub struct Coverage {
+    //every rule of the name parts and plural last names in the order of the rules file
+    pub rules: Vec<RuleUsage>,
+    pub unmatched: Vec<Unmatched>,
+    //number of checked words
+    pub words: usize,
+    //position in `rules` by section and index of a rule
+    positions: HashMap<(String, usize), usize>,
+}
+
+impl Coverage {
+    // Forms are not counted, a corpus has no forms
+    pub(crate) fn of(rules: &Rules) -> Coverage {
+        let groups = [
+            rules.last_name(),
+            rules.first_name(),
+            rules.middle_name(),
+            rules.last_name_plural(),
+        ];
+        let mut rules: Vec<RuleUsage> = groups
+            .iter()
+            .flat_map(|rule_group| rule_group.rules())
+            .map(|rule| RuleUsage {
+                rule: rule.info(),
+                count: 0,
+            })
+            .collect();
+        // Built-in rules have no line and go last
+        rules.sort_by_key(|usage| usage.rule.line.unwrap_or(usize::MAX));
+        let positions = rules
+            .iter()
+            .enumerate()
+            .map(|(position, usage)| ((usage.rule.section.clone(), usage.rule.index), position))
+            .collect();
+        Coverage {
+            rules,
+            unmatched: Vec::new(),
+            words: 0,
+            positions,
+        }
+    }
+
+    // Words matched by overrides are counted as matched, but overrides are not reported
+    pub(crate) fn add(&mut self, entry: &CorpusEntry, inflection: &Inflection) {
+        for word in &inflection.words {
+            self.words += 1;
+            match &word.rule {
+                Some(rule) => {
+                    let key = (rule.section.clone(), rule.index);
+                    if let Some(position) = self.positions.get(&key) {
+                        self.rules[*position].count += 1;
+                    }
+                }
+                None if word.kind == MatchKind::NoRule => self.unmatched.push(Unmatched {
+                    word: word.value.clone(),
+                    entry: entry.clone(),
+                }),
+                None => {}
+            }
+        }
+    }
+
+    pub fn unused(&self) -> impl Iterator<Item = &RuleUsage> {
+        self.rules.iter().filter(|usage| usage.count == 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Petrovich;
+
+    const RULES: &str = "
+lastname:
+  exceptions:
+    - gender: androgynous
+      test: [бонч]
+      mods: [., ., ., ., .]
+  suffixes:
+    - gender: male
+      test: [ов, ев]
+      mods: [а, у, а, ым, е]
+    - gender: female
+      test: [ова]
+      mods: [-ой, -ой, -у, -ой, -ой]
+    - gender: male
+      test: [ин]
+      mods: [а, у, а, ым, е]
+  plural:
+    suffixes:
+      - gender: androgynous
+        test: [ов]
+        mods: [ы, ых, ым, ых, ыми, ых]
+";
+
+    fn entry(name: &str, gender: Gender, number: Number) -> CorpusEntry {
+        CorpusEntry {
+            part: NamePart::LastName,
+            name: name.to_string(),
+            gender,
+            number,
+        }
+    }
+
+    #[test]
+    fn coverage_test() {
+        let petrovich: Petrovich = RULES.parse().unwrap();
+        let corpus = [
+            entry("Иванов", Gender::Male, Number::Singular),
+            entry("Лебедев", Gender::Male, Number::Singular),
+            entry("Бонч-Бруевич", Gender::Male, Number::Singular),
+            entry("Иванов", Gender::Male, Number::Plural),
+        ];
+        let coverage = petrovich.coverage(&corpus);
+        assert_eq!(5, coverage.words);
+
+        let counts: Vec<(String, usize)> = coverage
+            .rules
+            .iter()
+            .filter(|usage| usage.rule.line.is_some())
+            .map(|usage| (usage.rule.location(), usage.count))
+            .collect();
+        let expected = vec![
+            ("lastname.exceptions[0] (line 4)".to_string(), 1),
+            ("lastname.suffixes[0] (line 8)".to_string(), 2),
+            ("lastname.suffixes[1] (line 11)".to_string(), 0),
+            ("lastname.suffixes[2] (line 14)".to_string(), 0),
+            ("lastname.plural.suffixes[0] (line 19)".to_string(), 1),
+        ];
+        assert_eq!(expected, counts);
+
+        let unused: Vec<usize> = coverage
+            .unused()
+            .filter(|usage| usage.rule.section == "lastname.suffixes")
+            .map(|usage| usage.rule.index)
+            .collect();
+        assert_eq!(vec![1, 2], unused);
+
+        let expected = vec![Unmatched {
+            word: "бруевич".to_string(),
+            entry: corpus[2].clone(),
+        }];
+        assert_eq!(expected, coverage.unmatched);
+    }
+}
@@ -0,0 +1,140 @@
+use crate::name::FullName;
+
+//Non-breaking space, keeps initials on the same line with the last name
+pub const NBSP: &str = "\u{a0}";
+
+//Styles of a full name in documents
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum NameStyle {
+    //Иванову И. П.
+    LastNameInitials,
+    //И. П. Иванову
+    InitialsLastName,
+    //Иванову Ивану Петровичу
+    Full,
+    //Иванов, Иван Петрович
+    Sorting,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct NameFormat {
+    pub style: NameStyle,
+    //between the last name and the rest of the name
+    pub separator: String,
+    //between initials or between the first and the middle names
+    pub initials_separator: String,
+}
+
+impl NameFormat {
+    pub fn new(style: NameStyle) -> NameFormat {
+        NameFormat {
+            style,
+            separator: " ".to_string(),
+            initials_separator: " ".to_string(),
+        }
+    }
+
+    //Uses non-breaking spaces for all separators
+    pub fn non_breaking(style: NameStyle) -> NameFormat {
+        NameFormat {
+            style,
+            separator: NBSP.to_string(),
+            initials_separator: NBSP.to_string(),
+        }
+    }
+}
+
+impl FullName {
+    //Missing parts are skipped
+    pub fn format(&self, format: &NameFormat) -> String {
+        let last_name = self.last_name.as_deref().unwrap_or_default();
+        let given_names: Vec<String> = match format.style {
+            NameStyle::LastNameInitials | NameStyle::InitialsLastName => self
+                .given_names()
+                .map(initials)
+                .filter(|initials| !initials.is_empty())
+                .collect(),
+            NameStyle::Full | NameStyle::Sorting => {
+                self.given_names().map(str::to_string).collect()
+            }
+        };
+        let given_names = given_names.join(&format.initials_separator);
+        let (first, second) = match format.style {
+            NameStyle::InitialsLastName => (given_names.as_str(), last_name),
+            NameStyle::Sorting if !last_name.is_empty() && !given_names.is_empty() => {
+                return format!("{},{}{}", last_name, format.separator, given_names);
+            }
+            _ => (last_name, given_names.as_str()),
+        };
+        match (first.is_empty(), second.is_empty()) {
+            (true, _) => second.to_string(),
+            (_, true) => first.to_string(),
+            _ => format!("{}{}{}", first, format.separator, second),
+        }
+    }
+
+    fn given_names(&self) -> impl Iterator<Item = &str> {
+        [&self.first_name, &self.middle_name]
+            .into_iter()
+            .filter_map(|name| name.as_deref())
+    }
+}
+
+//Initials of a name, compound names keep the hyphen: "Анна-Мария" -> "А.-М."
+pub fn initials(name: &str) -> String {
+    let words: Vec<String> = name
+        .trim()
+        .split('-')
+        .filter_map(|word| word.chars().next())
+        .map(|c| format!("{}.", c.to_uppercase()))
+        .collect();
+    words.join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_test() {
+        let name = FullName::new("Иванову", "Ивану", "Петровичу");
+        let format = |style| name.format(&NameFormat::new(style));
+        assert_eq!("Иванову И. П.", format(NameStyle::LastNameInitials));
+        assert_eq!("И. П. Иванову", format(NameStyle::InitialsLastName));
+        assert_eq!("Иванову Ивану Петровичу", format(NameStyle::Full));
+        assert_eq!("Иванову, Ивану Петровичу", format(NameStyle::Sorting));
+    }
+
+    #[test]
+    fn format_separators_test() {
+        let name = FullName::new("Иванова", "Анна-Мария", "Петровна");
+        let format = NameFormat::non_breaking(NameStyle::LastNameInitials);
+        assert_eq!("Иванова\u{a0}А.-М.\u{a0}П.", name.format(&format));
+        let format = NameFormat {
+            initials_separator: "".to_string(),
+            ..NameFormat::new(NameStyle::InitialsLastName)
+        };
+        assert_eq!("А.-М.П. Иванова", name.format(&format));
+    }
+
+    #[test]
+    fn format_missing_parts_test() {
+        let name = FullName::new("Иванов", "Иван", "");
+        let format = |name: &FullName, style| name.format(&NameFormat::new(style));
+        assert_eq!("Иванов И.", format(&name, NameStyle::LastNameInitials));
+        assert_eq!("Иванов, Иван", format(&name, NameStyle::Sorting));
+        let name = FullName::new("Иванов", "", "");
+        assert_eq!("Иванов", format(&name, NameStyle::InitialsLastName));
+        assert_eq!("Иванов", format(&name, NameStyle::Sorting));
+        let name = FullName::new("", "Иван", "Петрович");
+        assert_eq!("И. П.", format(&name, NameStyle::LastNameInitials));
+        assert_eq!("Иван Петрович", format(&name, NameStyle::Sorting));
+    }
+
+    #[test]
+    fn initials_test() {
+        assert_eq!("И.", initials("иван"));
+        assert_eq!("А.-М.", initials("Анна-Мария"));
+        assert_eq!("", initials(""));
+    }
+}
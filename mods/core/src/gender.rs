@@ -7,7 +7,7 @@ const MALE: &str = "male";
 const FEMALE: &str = "female";
 const ANDROGYNOUS: &str = "androgynous";
 //Gender constants
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone, Copy)]
 pub enum Gender {
     Male,
     Female,
@@ -31,7 +31,9 @@ impl Gender {
             _ => None,
         }
     }
+    // The middle name is trimmed and lowercased: "ИВАНОВИЧ" -> Male
     pub fn detect_gender(middle_name: &str) -> Gender {
+        let middle_name = middle_name.trim().to_lowercase();
         if middle_name.ends_with("ич") {
             return Gender::Male;
         }
@@ -75,6 +77,12 @@ mod tests {
         assert_eq!(Gender::Female, actual);
     }
 
+    #[test]
+    fn detect_uppercase() {
+        assert_eq!(Gender::Male, Gender::detect_gender(" АЛЕКСЕЕВИЧ "));
+        assert_eq!(Gender::Female, Gender::detect_gender("АлексеевнА"));
+    }
+
     #[test]
     fn detect_androgynous() {
         let actual = Gender::detect_gender("Бильжо");
@@ -0,0 +1,208 @@
+use crate::gender::Gender;
+use std::fmt;
+use std::fmt::Display;
+
+//How the rule for a word was found
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum MatchKind {
+    //the word is listed in exceptions
+    Exception,
+    //the word ends with a suffix of a rule
+    Suffix,
+    //the matched rule keeps the word unchanged in every case
+    Indeclinable,
+    //no rule matched, the word is returned unchanged
+    NoRule,
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy)]
+pub enum Confidence {
+    Low,
+    Medium,
+    High,
+}
+
+//The rule of the rules file which inflected a word
+#[derive(PartialEq, Debug, Clone)]
+pub struct RuleInfo {
+    pub gender: Gender,
+    pub test: Vec<String>,
+    pub mods: Vec<String>,
+    pub first_word: bool,
+    //section of the rules file, e.g. "lastname.suffixes"
+    pub section: String,
+    //index of the rule in the section
+    pub index: usize,
+    //line of the rules file, None for built-in rules
+    pub line: Option<usize>,
+}
+
+//Inflection of a single word of a (possibly hyphenated) name
+#[derive(PartialEq, Debug, Clone)]
+pub struct WordInflection {
+    pub value: String,
+    pub kind: MatchKind,
+    //test string of the matched rule
+    pub test: Option<String>,
+    pub rule: Option<RuleInfo>,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Inflection {
+    pub value: String,
+    pub words: Vec<WordInflection>,
+}
+
+impl RuleInfo {
+    //"lastname.suffixes[3] (line 40)", built-in rules have no line
+    pub fn location(&self) -> String {
+        match self.line {
+            Some(line) => format!("{}[{}] (line {})", self.section, self.index, line),
+            None => format!("{}[{}]", self.section, self.index),
+        }
+    }
+}
+
+impl WordInflection {
+    pub fn confidence(&self) -> Confidence {
+        match self.kind {
+            MatchKind::Exception => Confidence::High,
+            // Single letter suffixes are the most general rules
+            MatchKind::Suffix => match &self.test {
+                Some(test) if test.chars().count() > 1 => Confidence::High,
+                _ => Confidence::Medium,
+            },
+            MatchKind::Indeclinable => Confidence::Medium,
+            MatchKind::NoRule => Confidence::Low,
+        }
+    }
+}
+
+impl Inflection {
+    //The lowest confidence of the words
+    pub fn confidence(&self) -> Confidence {
+        self.words
+            .iter()
+            .map(WordInflection::confidence)
+            .min()
+            .unwrap_or(Confidence::Low)
+    }
+
+    //Whether some word has no rule
+    pub fn has_unknown_words(&self) -> bool {
+        self.words.iter().any(|word| word.kind == MatchKind::NoRule)
+    }
+}
+
+//Inflections of a name for both genders, used when the gender is unknown
+#[derive(PartialEq, Debug, Clone)]
+pub enum GenderAlternatives<T> {
+    //the inflection does not depend on the gender
+    Same(T),
+    Different { male: T, female: T },
+}
+
+impl<T: PartialEq> GenderAlternatives<T> {
+    //Collapses equal inflections
+    pub fn of(male: T, female: T) -> GenderAlternatives<T> {
+        if male == female {
+            GenderAlternatives::Same(male)
+        } else {
+            GenderAlternatives::Different { male, female }
+        }
+    }
+}
+
+impl<T> GenderAlternatives<T> {
+    pub fn male(&self) -> &T {
+        match self {
+            GenderAlternatives::Same(value) => value,
+            GenderAlternatives::Different { male, .. } => male,
+        }
+    }
+
+    pub fn female(&self) -> &T {
+        match self {
+            GenderAlternatives::Same(value) => value,
+            GenderAlternatives::Different { female, .. } => female,
+        }
+    }
+
+    pub fn get(&self, gender: &Gender) -> Option<&T> {
+        match (self, gender) {
+            (GenderAlternatives::Same(value), _) => Some(value),
+            (_, Gender::Male) => Some(self.male()),
+            (_, Gender::Female) => Some(self.female()),
+            (_, Gender::Androgynous) => None,
+        }
+    }
+
+    pub fn is_ambiguous(&self) -> bool {
+        matches!(self, GenderAlternatives::Different { .. })
+    }
+}
+
+impl Display for Inflection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", &self.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(kind: MatchKind, test: Option<&str>) -> WordInflection {
+        WordInflection {
+            value: String::new(),
+            kind,
+            test: test.map(str::to_string),
+            rule: None,
+        }
+    }
+
+    #[test]
+    fn confidence_test() {
+        assert_eq!(
+            Confidence::High,
+            word(MatchKind::Exception, Some("лев")).confidence()
+        );
+        assert_eq!(
+            Confidence::High,
+            word(MatchKind::Suffix, Some("ов")).confidence()
+        );
+        assert_eq!(
+            Confidence::Medium,
+            word(MatchKind::Suffix, Some("а")).confidence()
+        );
+        assert_eq!(
+            Confidence::Medium,
+            word(MatchKind::Indeclinable, Some("о")).confidence()
+        );
+        assert_eq!(Confidence::Low, word(MatchKind::NoRule, None).confidence());
+
+        let inflection = Inflection {
+            value: String::new(),
+            words: vec![
+                word(MatchKind::Exception, Some("бонч")),
+                word(MatchKind::NoRule, None),
+            ],
+        };
+        assert_eq!(Confidence::Low, inflection.confidence());
+        assert!(inflection.has_unknown_words());
+    }
+
+    #[test]
+    fn gender_alternatives_test() {
+        let same = GenderAlternatives::of("саши", "саши");
+        assert_eq!(GenderAlternatives::Same("саши"), same);
+        assert!(!same.is_ambiguous());
+        assert_eq!(Some(&"саши"), same.get(&Gender::Androgynous));
+
+        let different = GenderAlternatives::of("жука", "жук");
+        assert!(different.is_ambiguous());
+        assert_eq!(&"жука", different.male());
+        assert_eq!(Some(&"жук"), different.get(&Gender::Female));
+        assert_eq!(None, different.get(&Gender::Androgynous));
+    }
+}
@@ -1,50 +1,790 @@
 pub mod case;
+pub mod coverage;
 pub mod errors;
+pub mod format;
 pub mod gender;
+pub mod inflection;
+pub mod lint;
+pub mod name;
+pub mod normalize;
+pub mod number;
+pub mod overlay;
+pub mod parse;
+mod position;
 mod rules;
+pub mod template;
 use case::Case;
+use coverage::{CorpusEntry, Coverage};
+use errors::PetrovichError;
 use gender::Gender;
-use rules::{RuleGroup, Rules};
+use inflection::{GenderAlternatives, Inflection, MatchKind, WordInflection};
+use lint::Lint;
+use name::{FullName, NamePart};
+use normalize::Normalization;
+use number::Number;
+use overlay::Overlay;
+use parse::ParsedName;
+use rules::{Rule, RuleGroup, RuleMatch, Rules};
+use std::borrow::Cow;
 use std::error::Error;
+use std::str::FromStr;
+
+#[cfg(feature = "embedded-rules")]
+pub const EMBEDDED_RULES: &str = include_str!("../petrovich-rules/rules.yml");
 
 #[derive(Debug)]
 pub struct Petrovich {
     rules: Rules,
+    normalization: Normalization,
 }
 
 impl Petrovich {
+    #[cfg(feature = "fs")]
     pub fn new(file_path: &str) -> Result<Petrovich, Box<dyn Error>> {
         let rules = Rules::load_from_file(file_path)?;
-        Result::Ok(Petrovich { rules })
+        Result::Ok(Petrovich {
+            rules,
+            normalization: Normalization::default(),
+        })
+    }
+
+    // Rules compiled into the library with the `embedded-rules` feature
+    #[cfg(feature = "embedded-rules")]
+    pub fn embedded() -> Result<Petrovich, Box<dyn Error>> {
+        EMBEDDED_RULES.parse()
+    }
+
+    pub fn normalization(&self) -> &Normalization {
+        &self.normalization
+    }
+
+    pub fn set_normalization(&mut self, normalization: Normalization) {
+        self.normalization = normalization;
+    }
+
+    // Overrides win over the rules, including exceptions. Replaces the previous overlay.
+    pub fn set_overlay(&mut self, overlay: &Overlay) {
+        self.rules.set_overlay(overlay);
     }
 
     pub fn first_name(&self, first_name: &str, gender: &Gender, case: &Case) -> String {
-        inflect(&self.rules.first_name(), first_name, case, gender)
+        self.inflect(&NamePart::FirstName, first_name, gender, case)
     }
 
     pub fn last_name(&self, last_name: &str, gender: &Gender, case: &Case) -> String {
-        inflect(&self.rules.last_name(), last_name, case, gender)
+        self.inflect(&NamePart::LastName, last_name, gender, case)
+    }
+
+    // Plural forms are used for families: "Ивановым", "семье Петровых".
+    pub fn last_name_with_number(
+        &self,
+        last_name: &str,
+        gender: &Gender,
+        case: &Case,
+        number: &Number,
+    ) -> String {
+        match number {
+            Number::Singular => self.last_name(last_name, gender, case),
+            Number::Plural => {
+                let rule_group = self.rules.last_name_plural();
+                self.apply_rules(rule_group, last_name, gender, |rule| {
+                    rule.get_plural_modifier(case)
+                })
+            }
+        }
     }
 
     pub fn middle_name(&self, middle_name: &str, gender: &Gender, case: &Case) -> String {
-        inflect(&self.rules.middle_name(), middle_name, case, gender)
+        self.inflect(&NamePart::MiddleName, middle_name, gender, case)
+    }
+
+    pub fn inflect(&self, part: &NamePart, name: &str, gender: &Gender, case: &Case) -> String {
+        self.apply_rules(self.rule_group(part), name, gender, |rule| {
+            rule.get_modifier(case)
+        })
+    }
+
+    // Appends the inflected name to `out`.
+    // Does not allocate if `out` has enough capacity and the name is in NFC.
+    pub fn inflect_into(
+        &self,
+        part: &NamePart,
+        name: &str,
+        gender: &Gender,
+        case: &Case,
+        out: &mut String,
+    ) {
+        self.write_rules(
+            self.rule_group(part),
+            name,
+            gender,
+            out,
+            |rule| rule.get_modifier(case),
+            |_, _| {},
+        )
+    }
+
+    // Borrows the name if the inflection leaves it unchanged,
+    // e.g. in the nominative case or for indeclinable names.
+    // As the result is lowercase, only lowercase names can be borrowed.
+    pub fn inflect_cow<'a>(
+        &self,
+        part: &NamePart,
+        name: &'a str,
+        gender: &Gender,
+        case: &Case,
+    ) -> Cow<'a, str> {
+        let rule_group = self.rule_group(part);
+        if self.is_unchanged(rule_group, name, gender, |rule| rule.get_modifier(case)) {
+            return Cow::Borrowed(name.trim());
+        }
+        let mut out = String::with_capacity(name.len() + 8);
+        self.inflect_into(part, name, gender, case, &mut out);
+        Cow::Owned(out)
+    }
+
+    // Same as `inflect` but also reports how the rule for every word was found,
+    // so rule misses can be told apart from indeclinable names.
+    pub fn inflect_detailed(
+        &self,
+        part: &NamePart,
+        name: &str,
+        gender: &Gender,
+        case: &Case,
+    ) -> Inflection {
+        self.apply_rules_detailed(self.rule_group(part), name, gender, |rule| {
+            rule.get_modifier(case)
+        })
+    }
+
+    // Unlike the methods for single name parts
+    // the result keeps the letter case of the given name: "Иванов" -> "Иванову".
+    pub fn full_name(&self, name: &FullName, gender: &Gender, case: &Case) -> FullName {
+        name.map(|part, value| {
+            let inflected = self.inflect(part, value, gender, case);
+            name::restore_case(value, &inflected)
+        })
+    }
+
+    // With `Gender::Androgynous` every rule matches and the first one in the rules file wins,
+    // so the name is inflected as male and as female instead: "Жук" -> "Жука" or "Жук".
+    pub fn inflect_alternatives(
+        &self,
+        part: &NamePart,
+        name: &str,
+        gender: &Gender,
+        case: &Case,
+    ) -> GenderAlternatives<String> {
+        match gender {
+            Gender::Androgynous => GenderAlternatives::of(
+                self.inflect(part, name, &Gender::Male, case),
+                self.inflect(part, name, &Gender::Female, case),
+            ),
+            _ => GenderAlternatives::Same(self.inflect(part, name, gender, case)),
+        }
+    }
+
+    // The gender is detected by the middle name if `Gender::Androgynous` is given.
+    pub fn full_name_alternatives(
+        &self,
+        name: &FullName,
+        gender: &Gender,
+        case: &Case,
+    ) -> GenderAlternatives<FullName> {
+        let gender = match gender {
+            Gender::Androgynous => name.gender(),
+            _ => *gender,
+        };
+        match gender {
+            Gender::Androgynous => GenderAlternatives::of(
+                self.full_name(name, &Gender::Male, case),
+                self.full_name(name, &Gender::Female, case),
+            ),
+            _ => GenderAlternatives::Same(self.full_name(name, &gender, case)),
+        }
+    }
+
+    // Splits a free-form string like "Иванов Иван Иванович" or "ИВАНОВ И.И." into name parts.
+    // The best candidate can be passed to `full_name` as is.
+    pub fn parse_name(&self, input: &str) -> Result<ParsedName, PetrovichError> {
+        parse::parse(input, self.rules.first_name(), &self.normalization)
+    }
+
+    // Counts the words of the corpus matched by every rule, the corpus is not inflected
+    pub fn coverage(&self, corpus: &[CorpusEntry]) -> Coverage {
+        let mut coverage = Coverage::of(&self.rules);
+        for entry in corpus {
+            let rule_group = match (&entry.part, &entry.number) {
+                (NamePart::LastName, Number::Plural) => self.rules.last_name_plural(),
+                (part, _) => self.rule_group(part),
+            };
+            let inflection =
+                self.apply_rules_detailed(rule_group, &entry.name, &entry.gender, |_| None);
+            coverage.add(entry, &inflection);
+        }
+        coverage
+    }
+
+    // Problems of the rules file: rules that can never match or contradict earlier ones
+    pub fn lint(&self) -> Vec<Lint> {
+        lint::lint(&self.rules)
+    }
+
+    pub fn first_name_form(&self, first_name: &str, gender: &Gender, form: &str) -> Option<String> {
+        self.inflect_form(&NamePart::FirstName, first_name, gender, form)
+    }
+
+    pub fn last_name_form(&self, last_name: &str, gender: &Gender, form: &str) -> Option<String> {
+        self.inflect_form(&NamePart::LastName, last_name, gender, form)
+    }
+
+    pub fn middle_name_form(
+        &self,
+        middle_name: &str,
+        gender: &Gender,
+        form: &str,
+    ) -> Option<String> {
+        self.inflect_form(&NamePart::MiddleName, middle_name, gender, form)
+    }
+
+    // Additional named forms (e.g. `case::VOCATIVE`) supplied by the rules file
+    // in the `forms` section of a name part.
+    // Returns `None` if there is no such form for the name part.
+    pub fn inflect_form(
+        &self,
+        part: &NamePart,
+        name: &str,
+        gender: &Gender,
+        form: &str,
+    ) -> Option<String> {
+        let rule_group = self.rule_group(part).form(form)?;
+        let result = self.apply_rules(rule_group, name, gender, Rule::get_form_modifier);
+        Some(result)
+    }
+
+    fn rule_group(&self, part: &NamePart) -> &RuleGroup {
+        match part {
+            NamePart::LastName => self.rules.last_name(),
+            NamePart::FirstName => self.rules.first_name(),
+            NamePart::MiddleName => self.rules.middle_name(),
+        }
+    }
+
+    fn apply_rules<'r, F>(
+        &self,
+        rule_group: &'r RuleGroup,
+        name: &str,
+        gender: &Gender,
+        modifier: F,
+    ) -> String
+    where
+        F: Fn(&'r Rule) -> Option<&'r String>,
+    {
+        let mut out = String::with_capacity(name.len() + 8);
+        self.write_rules(rule_group, name, gender, &mut out, modifier, |_, _| {});
+        out
     }
-}
 
-fn inflect(rule_group: &RuleGroup, name: &str, case: &Case, gender: &Gender) -> String {
-    let name = name.trim().to_lowercase();
-    let name_vec: Vec<&str> = name.split('-').collect();
-    let mut result: Vec<String> = Vec::with_capacity(name_vec.len());
-    let mut is_first = name_vec.len() > 1;
-    for word in name_vec {
-        let rule = rule_group.get_rule(word, gender, is_first);
-        match rule {
-            None => result.push(word.to_string()),
-            Some(rule) => result.push(rule.apply(word, case)),
-        }
-        if is_first {
+    // `on_word` gets the rule match of every word and the inflected word written to `out`
+    fn write_rules<'r, F, W>(
+        &self,
+        rule_group: &'r RuleGroup,
+        name: &str,
+        gender: &Gender,
+        out: &mut String,
+        modifier: F,
+        mut on_word: W,
+    ) where
+        F: Fn(&'r Rule) -> Option<&'r String>,
+        W: FnMut(Option<RuleMatch<'r>>, &str),
+    {
+        let name = self.normalization.prepare(name);
+        let mut is_first = name.contains('-');
+        for (i, word) in name.split('-').enumerate() {
+            if i > 0 {
+                out.push('-');
+            }
+            // Rules are searched by the normalized key,
+            // but modifiers are applied to the word as it was written.
+            // The key is written to `out` and then replaced by the word to avoid allocations.
+            let start = out.len();
+            self.normalization.push_match_key(word, out);
+            let rule_match =
+                rule_group.find(&out[start..], gender, is_first, self.normalization.fold_yo);
+            out.truncate(start);
+            out.extend(word.chars().flat_map(char::to_lowercase));
+            let modifier = rule_match.as_ref().and_then(|m| modifier(m.rule));
+            // A modifier longer than the word keeps it, the word is reported as matched by no rule
+            if modifier.is_some_and(|modifier| !rules::apply_modifier_into(out, start, modifier)) {
+                on_word(None, &out[start..]);
+            } else {
+                on_word(rule_match, &out[start..]);
+            }
             is_first = false;
         }
     }
-    result.join("-")
+
+    // Whether the name is its own inflection, checked without allocations
+    fn is_unchanged<'r, F>(
+        &self,
+        rule_group: &'r RuleGroup,
+        name: &str,
+        gender: &Gender,
+        modifier: F,
+    ) -> bool
+    where
+        F: Fn(&'r Rule) -> Option<&'r String>,
+    {
+        let name = match self.normalization.prepare(name) {
+            Cow::Borrowed(name) => name,
+            Cow::Owned(_) => return false,
+        };
+        let mut is_first = name.contains('-');
+        for word in name.split('-') {
+            if !self.normalization.is_match_key(word) {
+                return false;
+            }
+            let rule_match = rule_group.find(word, gender, is_first, self.normalization.fold_yo);
+            let modifier = rule_match.and_then(|rule_match| modifier(rule_match.rule));
+            if modifier.is_some_and(|modifier| modifier.chars().any(|c| c != '.')) {
+                return false;
+            }
+            is_first = false;
+        }
+        true
+    }
+
+    fn apply_rules_detailed<'r, F>(
+        &self,
+        rule_group: &'r RuleGroup,
+        name: &str,
+        gender: &Gender,
+        modifier: F,
+    ) -> Inflection
+    where
+        F: Fn(&'r Rule) -> Option<&'r String>,
+    {
+        let mut words: Vec<WordInflection> = Vec::new();
+        let mut value = String::with_capacity(name.len() + 8);
+        self.write_rules(
+            rule_group,
+            name,
+            gender,
+            &mut value,
+            modifier,
+            |rule_match, word| {
+                words.push(match rule_match {
+                    None => WordInflection {
+                        value: word.to_string(),
+                        kind: MatchKind::NoRule,
+                        test: None,
+                        rule: None,
+                    },
+                    Some(rule_match) => WordInflection {
+                        value: word.to_string(),
+                        kind: if rule_match.rule.is_indeclinable() {
+                            MatchKind::Indeclinable
+                        } else if rule_match.exception {
+                            MatchKind::Exception
+                        } else {
+                            MatchKind::Suffix
+                        },
+                        test: Some(rule_match.test.to_string()),
+                        rule: Some(rule_match.rule.info()),
+                    },
+                })
+            },
+        );
+        Inflection { value, words }
+    }
+}
+
+// Loads rules from the content of a rules file
+impl FromStr for Petrovich {
+    type Err = Box<dyn Error>;
+
+    fn from_str(content: &str) -> Result<Self, Self::Err> {
+        let rules = Rules::load_from_str(content)?;
+        Result::Ok(Petrovich {
+            rules,
+            normalization: Normalization::default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use inflection::Confidence;
+    use overlay::Override;
+
+    fn test_petrovich(normalization: Normalization) -> Petrovich {
+        let rules = Rules::load_from_str(
+            "
+firstname:
+  exceptions:
+    - gender: male
+      test: [пётр]
+      mods: [---етра, ---етру, ---етра, ---етром, ---етре]
+  suffixes:
+    - gender: female
+      test: [а]
+      mods: [-ы, -е, -у, -ой, -е]
+lastname:
+  exceptions:
+    - gender: androgynous
+      test: [бонч]
+      mods: [., ., ., ., .]
+      tags: [first_word]
+  suffixes:
+    - gender: male
+      test: [ов]
+      mods: [а, у, а, ым, е]
+    - gender: androgynous
+      test: [о]
+      mods: [., ., ., ., .]
+",
+        )
+        .unwrap();
+        Petrovich {
+            rules,
+            normalization,
+        }
+    }
+
+    #[test]
+    fn inflect_normalized_test() {
+        let petrovich = test_petrovich(Normalization::default());
+        let actual = petrovich.last_name("Ивaнoв", &Gender::Male, &Case::Dative);
+        assert_eq!("ивaнoву", actual);
+        let actual = petrovich.first_name("Пе\u{0308}тр", &Gender::Male, &Case::Dative);
+        assert_eq!("петру", actual);
+        let actual = petrovich.first_name("Петр", &Gender::Male, &Case::Dative);
+        assert_eq!("петр", actual);
+    }
+
+    #[test]
+    fn inflect_fold_yo_test() {
+        let mut petrovich = test_petrovich(Normalization::default());
+        petrovich.set_normalization(Normalization {
+            fold_yo: true,
+            ..Normalization::default()
+        });
+        let actual = petrovich.first_name("Петр", &Gender::Male, &Case::Genitive);
+        assert_eq!("петра", actual);
+        let actual = petrovich.first_name("Алёна", &Gender::Female, &Case::Genitive);
+        assert_eq!("алёны", actual);
+    }
+
+    #[test]
+    fn last_name_plural_test() {
+        let petrovich = test_petrovich(Normalization::default());
+        let plural = |name: &str, gender: &Gender, case: &Case| {
+            petrovich.last_name_with_number(name, gender, case, &Number::Plural)
+        };
+        assert_eq!(
+            "ивановы",
+            plural("Иванов", &Gender::Male, &Case::Nominative)
+        );
+        assert_eq!(
+            "ивановым",
+            plural("Иванова", &Gender::Female, &Case::Dative)
+        );
+        assert_eq!(
+            "петровых",
+            plural("Петров", &Gender::Androgynous, &Case::Genitive)
+        );
+        assert_eq!(
+            "пушкиными",
+            plural("Пушкин", &Gender::Male, &Case::Instrumental)
+        );
+        assert_eq!(
+            "достоевских",
+            plural("Достоевский", &Gender::Male, &Case::Prepositional)
+        );
+        assert_eq!(
+            "толстые",
+            plural("Толстая", &Gender::Female, &Case::Nominative)
+        );
+        assert_eq!(
+            "римских-корсаковых",
+            plural("Римский-Корсаков", &Gender::Male, &Case::Genitive)
+        );
+        assert_eq!("черных", plural("Черных", &Gender::Male, &Case::Dative));
+        assert_eq!("дурново", plural("Дурново", &Gender::Male, &Case::Dative));
+
+        let actual = petrovich.last_name_with_number(
+            "Иванов",
+            &Gender::Male,
+            &Case::Dative,
+            &Number::Singular,
+        );
+        assert_eq!("иванову", actual);
+    }
+
+    #[test]
+    fn vocative_test() {
+        let petrovich = test_petrovich(Normalization::default());
+        let vocative =
+            |name: &str, gender: &Gender| petrovich.first_name_form(name, gender, case::VOCATIVE);
+        assert_eq!(Some("маш".to_string()), vocative("Маша", &Gender::Female));
+        assert_eq!(Some("саш".to_string()), vocative("Саша", &Gender::Male));
+        assert_eq!(Some("тань".to_string()), vocative("Таня", &Gender::Female));
+        assert_eq!(
+            Some("мария".to_string()),
+            vocative("Мария", &Gender::Female)
+        );
+        assert_eq!(Some("илья".to_string()), vocative("Илья", &Gender::Male));
+        assert_eq!(Some("иван".to_string()), vocative("Иван", &Gender::Male));
+        assert_eq!(
+            None,
+            petrovich.middle_name_form("Ивановна", &Gender::Female, case::VOCATIVE)
+        );
+    }
+
+    #[test]
+    fn custom_form_test() {
+        let mut petrovich = test_petrovich(Normalization::default());
+        petrovich.rules = Rules::load_from_str(
+            "
+lastname:
+  suffixes: []
+  forms:
+    locative:
+      suffixes:
+        - gender: androgynous
+          test: [ов]
+          mods: [е]
+firstname:
+  forms:
+    vocative:
+      suffixes:
+        - gender: androgynous
+          test: [а]
+          mods: [.]
+",
+        )
+        .unwrap();
+        let actual = petrovich.last_name_form("Петров", &Gender::Male, "locative");
+        assert_eq!(Some("петрове".to_string()), actual);
+        let actual = petrovich.first_name_form("Маша", &Gender::Female, case::VOCATIVE);
+        assert_eq!(Some("маша".to_string()), actual);
+        let actual = petrovich.first_name_form("Маша", &Gender::Female, "partitive");
+        assert_eq!(None, actual);
+    }
+
+    #[test]
+    fn full_name_test() {
+        let petrovich = test_petrovich(Normalization::default());
+        let name = FullName::new("ИВАНОВ", "Пётр", "");
+        let actual = petrovich.full_name(&name, &Gender::Male, &Case::Dative);
+        assert_eq!(FullName::new("ИВАНОВУ", "Петру", ""), actual);
+    }
+
+    #[test]
+    fn gender_alternatives_test() {
+        let petrovich: Petrovich = "
+firstname:
+  suffixes:
+    - gender: androgynous
+      test: [ша]
+      mods: [-и, -е, -у, -ей, -е]
+lastname:
+  suffixes:
+    - gender: female
+      test: [к]
+      mods: [., ., ., ., .]
+    - gender: male
+      test: [к]
+      mods: [а, у, а, ом, е]
+"
+        .parse()
+        .unwrap();
+        let last_name = |gender| {
+            petrovich.inflect_alternatives(&NamePart::LastName, "Жук", gender, &Case::Genitive)
+        };
+        let expected = GenderAlternatives::Different {
+            male: "жука".to_string(),
+            female: "жук".to_string(),
+        };
+        assert_eq!(expected, last_name(&Gender::Androgynous));
+        let expected = GenderAlternatives::Same("жука".to_string());
+        assert_eq!(expected, last_name(&Gender::Male));
+        let actual = petrovich.inflect_alternatives(
+            &NamePart::FirstName,
+            "Саша",
+            &Gender::Androgynous,
+            &Case::Genitive,
+        );
+        assert_eq!(GenderAlternatives::Same("саши".to_string()), actual);
+
+        let name = FullName::new("Жук", "Саша", "");
+        let actual = petrovich.full_name_alternatives(&name, &Gender::Androgynous, &Case::Dative);
+        assert_eq!(&FullName::new("Жуку", "Саше", ""), actual.male());
+        assert_eq!(&FullName::new("Жук", "Саше", ""), actual.female());
+        // the middle name tells the gender
+        let name = FullName::new("Жук", "Саша", "Петровна");
+        let actual = petrovich.full_name_alternatives(&name, &Gender::Androgynous, &Case::Dative);
+        assert_eq!(
+            GenderAlternatives::Same(FullName::new("Жук", "Саше", "Петровна")),
+            actual
+        );
+    }
+
+    #[test]
+    fn parse_name_test() {
+        let petrovich = test_petrovich(Normalization::default());
+        let parsed = petrovich.parse_name("Пётр Иванов").unwrap().best;
+        let actual = petrovich.full_name(&parsed.name, &parsed.gender, &Case::Genitive);
+        assert_eq!(FullName::new("Иванова", "Петра", ""), actual);
+    }
+
+    #[test]
+    fn inflect_detailed_test() {
+        let petrovich = test_petrovich(Normalization::default());
+        let detailed = |part: &NamePart, name: &str| {
+            petrovich.inflect_detailed(part, name, &Gender::Male, &Case::Dative)
+        };
+        let actual = detailed(&NamePart::LastName, "Бонч-Бруевич");
+        assert_eq!("бонч-бруевич", actual.value);
+        assert_eq!(MatchKind::Indeclinable, actual.words[0].kind);
+        assert_eq!(Some("бонч".to_string()), actual.words[0].test);
+        assert_eq!(MatchKind::NoRule, actual.words[1].kind);
+        assert_eq!(Confidence::Low, actual.confidence());
+
+        let actual = detailed(&NamePart::LastName, "Петров");
+        assert_eq!("петрову", actual.value);
+        assert_eq!(MatchKind::Suffix, actual.words[0].kind);
+        assert_eq!(Confidence::High, actual.confidence());
+        let rule = actual.words[0].rule.as_ref().unwrap();
+        assert_eq!("lastname.suffixes", rule.section);
+        assert_eq!((0, Some(18)), (rule.index, rule.line));
+        assert_eq!(vec!["а", "у", "а", "ым", "е"], rule.mods);
+        assert_eq!(Gender::Male, rule.gender);
+
+        let actual = detailed(&NamePart::LastName, "Дурново");
+        assert_eq!(MatchKind::Indeclinable, actual.words[0].kind);
+        assert_eq!(Confidence::Medium, actual.confidence());
+
+        let actual = detailed(&NamePart::FirstName, "Пётр");
+        assert_eq!(MatchKind::Exception, actual.words[0].kind);
+        assert_eq!(Confidence::High, actual.confidence());
+    }
+
+    #[test]
+    fn long_modifier_test() {
+        let petrovich: Petrovich = "
+lastname:
+  suffixes:
+    - gender: male
+      test: [ли]
+      mods: [---ьва, ---ьву, ---ьва, ---ьвом, ---ьве]
+"
+        .parse()
+        .unwrap();
+        let actual = petrovich.inflect_detailed(
+            &NamePart::LastName,
+            "Ли-Шали",
+            &Gender::Male,
+            &Case::Dative,
+        );
+        assert_eq!("ли-шьву", actual.value);
+        assert_eq!(MatchKind::NoRule, actual.words[0].kind);
+        assert_eq!(None, actual.words[0].rule);
+        assert_eq!(MatchKind::Suffix, actual.words[1].kind);
+        let actual = petrovich.last_name("Ли", &Gender::Male, &Case::Dative);
+        assert_eq!("ли", actual);
+    }
+
+    #[test]
+    fn overlay_test() {
+        let mut petrovich = test_petrovich(Normalization::default());
+        let mut overlay = Overlay::default();
+        let forms = ["пётра", "пётру", "пётра", "пётром", "пётре"];
+        let petr = Override::new(NamePart::FirstName, Gender::Male, "пётр", forms).unwrap();
+        overlay.add(petr);
+        let forms = ["петровой", "петровой", "петрову", "петровой", "петровой"];
+        let petrova = Override::new(NamePart::LastName, Gender::Female, "петрова", forms);
+        overlay.add(petrova.unwrap());
+        petrovich.set_overlay(&overlay);
+
+        let name = FullName::new("Петрова", "Пётр", "");
+        let actual = petrovich.full_name(&name, &Gender::Male, &Case::Dative);
+        assert_eq!(FullName::new("Петрова", "Пётру", ""), actual);
+        let actual = petrovich.last_name("Петрова", &Gender::Female, &Case::Accusative);
+        assert_eq!("петрову", actual);
+        // whole words only
+        let actual = petrovich.last_name("Сапетрова", &Gender::Female, &Case::Accusative);
+        assert_eq!("сапетрова", actual);
+        let actual =
+            petrovich.inflect_detailed(&NamePart::FirstName, "Пётр", &Gender::Male, &Case::Dative);
+        let rule = actual.words[0].rule.as_ref().unwrap();
+        assert_eq!(
+            ("firstname.overrides", None),
+            (rule.section.as_str(), rule.line)
+        );
+
+        petrovich.set_overlay(&Overlay::default());
+        let actual = petrovich.first_name("Пётр", &Gender::Male, &Case::Dative);
+        assert_eq!("петру", actual);
+    }
+
+    #[test]
+    fn inflect_into_test() {
+        let petrovich = test_petrovich(Normalization::default());
+        let mut out = String::from("Кому: ");
+        petrovich.inflect_into(
+            &NamePart::LastName,
+            "Петров",
+            &Gender::Male,
+            &Case::Dative,
+            &mut out,
+        );
+        assert_eq!("Кому: петрову", out);
+        out.clear();
+        let part = &NamePart::LastName;
+        petrovich.inflect_into(
+            part,
+            "Бонч-Ивaнoв",
+            &Gender::Male,
+            &Case::Genitive,
+            &mut out,
+        );
+        assert_eq!("бонч-ивaнoва", out);
+    }
+
+    #[test]
+    fn inflect_cow_test() {
+        let petrovich = test_petrovich(Normalization::default());
+        let cow =
+            |name, case| petrovich.inflect_cow(&NamePart::LastName, name, &Gender::Male, case);
+        assert!(matches!(
+            cow("петров", &Case::Nominative),
+            Cow::Borrowed("петров")
+        ));
+        assert!(matches!(
+            cow(" дурново ", &Case::Dative),
+            Cow::Borrowed("дурново")
+        ));
+        assert!(matches!(cow("смит", &Case::Dative), Cow::Borrowed("смит")));
+        assert_eq!(
+            Cow::<str>::Owned("петрову".to_string()),
+            cow("петров", &Case::Dative)
+        );
+        assert_eq!(
+            Cow::<str>::Owned("петров".to_string()),
+            cow("Петров", &Case::Nominative)
+        );
+        assert_eq!(
+            Cow::<str>::Owned("ивaнoву".to_string()),
+            cow("ивaнoв", &Case::Dative)
+        );
+    }
+
+    #[test]
+    fn inflect_without_normalization_test() {
+        let petrovich = test_petrovich(Normalization::none());
+        let actual = petrovich.last_name("Ивaнoв", &Gender::Male, &Case::Dative);
+        assert_eq!("ивaнoв", actual);
+    }
 }
@@ -0,0 +1,291 @@
+use crate::gender::Gender;
+use crate::inflection::RuleInfo;
+use crate::rules::{Rule, Rules};
+use std::fmt;
+use std::fmt::Display;
+
+const SHADOWED: &str = "shadowed";
+const DUPLICATE: &str = "duplicate";
+const CONFLICT: &str = "conflict";
+const MODS_LENGTH: &str = "mods-length";
+const UNMATCHABLE: &str = "unmatchable";
+
+//Kinds of problems of a rules file
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum LintKind {
+    //an earlier rule matches every word the test matches
+    Shadowed,
+    //an earlier rule or the rule itself has the same test
+    Duplicate,
+    //an earlier rule has the same test, but only one of them keeps words unchanged
+    Conflict,
+    //the number of mods differs from the number of cases of the section
+    ModsLength,
+    //the test is not an ending of a lowercase Cyrillic word
+    Unmatchable,
+}
+
+//A problem of a single rule
+#[derive(PartialEq, Debug, Clone)]
+pub struct Lint {
+    pub kind: LintKind,
+    pub rule: RuleInfo,
+    //test of the rule, None for wrong mods
+    pub test: Option<String>,
+    //the earlier rule and its test matching instead of this one
+    pub earlier: Option<(RuleInfo, String)>,
+}
+
+impl LintKind {
+    pub fn value(&self) -> &'static str {
+        match self {
+            LintKind::Shadowed => SHADOWED,
+            LintKind::Duplicate => DUPLICATE,
+            LintKind::Conflict => CONFLICT,
+            LintKind::ModsLength => MODS_LENGTH,
+            LintKind::Unmatchable => UNMATCHABLE,
+        }
+    }
+
+    pub fn of(kind: &str) -> Option<LintKind> {
+        match kind {
+            SHADOWED => Some(LintKind::Shadowed),
+            DUPLICATE => Some(LintKind::Duplicate),
+            CONFLICT => Some(LintKind::Conflict),
+            MODS_LENGTH => Some(LintKind::ModsLength),
+            UNMATCHABLE => Some(LintKind::Unmatchable),
+            _ => None,
+        }
+    }
+}
+
+impl Display for LintKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", &self.value())
+    }
+}
+
+//"lastname.suffixes[3] (line 40): shadowed: test 'ова' is matched by 'ва' of lastname.suffixes[1] (line 20)"
+impl Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}: ", self.rule.location(), self.kind)?;
+        let test = self.test.as_deref().unwrap_or_default();
+        match (&self.kind, &self.earlier) {
+            (LintKind::ModsLength, _) => write!(f, "{} mods", self.rule.mods.len()),
+            (LintKind::Unmatchable, _) => {
+                write!(f, "test '{}' can't match a lowercase Cyrillic word", test)
+            }
+            (_, Some((rule, _))) if rule == &self.rule => {
+                write!(f, "test '{}' is repeated in the rule", test)
+            }
+            (LintKind::Shadowed, Some((rule, earlier))) => write!(
+                f,
+                "test '{}' is matched by '{}' of {}",
+                test,
+                earlier,
+                rule.location()
+            ),
+            (LintKind::Conflict, Some((rule, _))) => write!(
+                f,
+                "test '{}' of {} has other mods, only one of them keeps words unchanged",
+                test,
+                rule.location()
+            ),
+            (_, Some((rule, _))) => write!(f, "test '{}' is already in {}", test, rule.location()),
+            (_, None) => write!(f, "test '{}'", test),
+        }
+    }
+}
+
+//Problems of every rule, in the order of the rules file. Overrides are not checked.
+pub(crate) fn lint(rules: &Rules) -> Vec<Lint> {
+    let mut result = Vec::new();
+    for (rule_group, mods) in rules.groups() {
+        let rules: Vec<RuleInfo> = rule_group.rules().map(Rule::info).collect();
+        lint_group(&rules, mods, &mut result);
+    }
+    // Built-in rules have no line and go last
+    result.sort_by_key(|lint| lint.rule.line.unwrap_or(usize::MAX));
+    result
+}
+
+// `rules` are in the order they are tried
+fn lint_group(rules: &[RuleInfo], mods: usize, result: &mut Vec<Lint>) {
+    for (index, rule) in rules.iter().enumerate() {
+        if rule.mods.len() != mods {
+            result.push(Lint {
+                kind: LintKind::ModsLength,
+                rule: rule.clone(),
+                test: None,
+                earlier: None,
+            });
+        }
+        for (position, test) in rule.test.iter().enumerate() {
+            let lint = |kind, earlier: Option<(&RuleInfo, &String)>| Lint {
+                kind,
+                rule: rule.clone(),
+                test: Some(test.clone()),
+                earlier: earlier.map(|(rule, test)| (rule.clone(), test.clone())),
+            };
+            if !is_matchable(test) {
+                result.push(lint(LintKind::Unmatchable, None));
+            } else if rule.test[..position].contains(test) {
+                result.push(lint(LintKind::Duplicate, Some((rule, test))));
+            } else if let Some((earlier, earlier_test)) = shadow_of(&rules[..index], rule, test) {
+                let kind = if earlier_test != test {
+                    LintKind::Shadowed
+                } else if is_indeclinable(earlier) != is_indeclinable(rule) {
+                    LintKind::Conflict
+                } else {
+                    LintKind::Duplicate
+                };
+                result.push(lint(kind, Some((earlier, earlier_test))));
+            }
+        }
+    }
+}
+
+// The first earlier rule matching every word the test matches, the same test is preferred.
+// Exceptions are matched as suffixes too, so they shadow later exceptions and suffixes alike.
+fn shadow_of<'a>(
+    earlier: &'a [RuleInfo],
+    rule: &RuleInfo,
+    test: &str,
+) -> Option<(&'a RuleInfo, &'a String)> {
+    earlier
+        .iter()
+        .filter(|other| covers(other, rule))
+        .find_map(|other| {
+            let same = other.test.iter().find(|other_test| *other_test == test);
+            same.or_else(|| {
+                other
+                    .test
+                    .iter()
+                    .find(|other_test| test.ends_with(other_test.as_str()))
+            })
+            .map(|other_test| (other, other_test))
+        })
+}
+
+// `other` matches words of every gender and position `rule` matches
+fn covers(other: &RuleInfo, rule: &RuleInfo) -> bool {
+    (other.gender == Gender::Androgynous || other.gender == rule.gender)
+        && (!other.first_word || rule.first_word)
+}
+
+fn is_indeclinable(rule: &RuleInfo) -> bool {
+    !rule.mods.is_empty() && rule.mods.iter().all(|modifier| modifier == ".")
+}
+
+// Words are lowercased and split at hyphens before matching
+fn is_matchable(test: &str) -> bool {
+    test.chars().all(|c| matches!(c, 'а'..='я' | 'ё'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Petrovich;
+
+    fn lints(rules: &str) -> Vec<String> {
+        let petrovich: Petrovich = rules.parse().unwrap();
+        petrovich.lint().iter().map(Lint::to_string).collect()
+    }
+
+    #[test]
+    fn lint_test() {
+        let actual = lints(
+            "
+lastname:
+  exceptions:
+    - gender: male
+      test: [бонч]
+      mods: [., ., ., ., .]
+  suffixes:
+    - gender: androgynous
+      test: [ва, ов]
+      mods: [., ., ., ., .]
+    - gender: female
+      test: [ова, ов]
+      mods: [-ой, -ой, -у, -ой, -ой]
+    - gender: male
+      test: [бонч, ин, ин]
+      mods: [а, у, а, ым]
+    - gender: male
+      test: [ok, ов]
+      mods: [а, у, а, ом, е]
+firstname:
+  suffixes:
+    - gender: male
+      test: [н]
+      tags: [first_word]
+      mods: [а, у, а, ом, е]
+    - gender: male
+      test: [ан]
+      mods: [а, у, а, ом, е]
+",
+        );
+        let expected = vec![
+            "lastname.suffixes[1] (line 11): shadowed: test 'ова' is matched by 'ва' of lastname.suffixes[0] (line 8)",
+            "lastname.suffixes[1] (line 11): conflict: test 'ов' of lastname.suffixes[0] (line 8) has other mods, only one of them keeps words unchanged",
+            "lastname.suffixes[2] (line 14): mods-length: 4 mods",
+            "lastname.suffixes[2] (line 14): conflict: test 'бонч' of lastname.exceptions[0] (line 4) has other mods, only one of them keeps words unchanged",
+            "lastname.suffixes[2] (line 14): duplicate: test 'ин' is repeated in the rule",
+            "lastname.suffixes[3] (line 17): unmatchable: test 'ok' can't match a lowercase Cyrillic word",
+            "lastname.suffixes[3] (line 17): conflict: test 'ов' of lastname.suffixes[0] (line 8) has other mods, only one of them keeps words unchanged",
+        ];
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn exceptions_test() {
+        let actual = lints(
+            "
+lastname:
+  exceptions:
+    - gender: androgynous
+      test: [ва]
+      mods: [., ., ., ., .]
+    - gender: androgynous
+      test: [ова]
+      mods: [., ., ., ., .]
+  suffixes:
+    - gender: female
+      test: [ва]
+      mods: [-ой, -ой, -у, -ой, -ой]
+",
+        );
+        let expected = vec![
+            "lastname.exceptions[1] (line 7): shadowed: test 'ова' is matched by 'ва' of lastname.exceptions[0] (line 4)",
+            "lastname.suffixes[0] (line 11): conflict: test 'ва' of lastname.exceptions[0] (line 4) has other mods, only one of them keeps words unchanged",
+        ];
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn sections_test() {
+        let actual = lints(
+            "
+lastname:
+  plural:
+    suffixes:
+      - gender: androgynous
+        test: [ы]
+        mods: [., ., ., ., .]
+  forms:
+    vocative:
+      suffixes:
+        - gender: androgynous
+          test: [а]
+          mods: [-, -]
+",
+        );
+        let expected = vec![
+            "lastname.plural.suffixes[0] (line 5): mods-length: 5 mods",
+            "lastname.forms.vocative.suffixes[0] (line 11): mods-length: 2 mods",
+        ];
+        assert_eq!(expected, actual);
+        assert_eq!(Some(LintKind::ModsLength), LintKind::of("mods-length"));
+        assert!(lints("{}").is_empty());
+    }
+}
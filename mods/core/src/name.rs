@@ -0,0 +1,185 @@
+use crate::gender::Gender;
+use std::fmt;
+use std::fmt::Display;
+
+const LAST_NAME: &str = "lastname";
+const FIRST_NAME: &str = "firstname";
+const MIDDLE_NAME: &str = "middlename";
+
+//Name part constants, values match sections of the rules file
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum NamePart {
+    //фамилия
+    LastName,
+    //имя
+    FirstName,
+    //отчество
+    MiddleName,
+}
+
+impl NamePart {
+    pub fn value(&self) -> &'static str {
+        match self {
+            NamePart::LastName => LAST_NAME,
+            NamePart::FirstName => FIRST_NAME,
+            NamePart::MiddleName => MIDDLE_NAME,
+        }
+    }
+
+    pub fn of(part: &str) -> Option<NamePart> {
+        match part {
+            LAST_NAME => Some(NamePart::LastName),
+            FIRST_NAME => Some(NamePart::FirstName),
+            MIDDLE_NAME => Some(NamePart::MiddleName),
+            _ => None,
+        }
+    }
+
+    //Parts in the usual order of a full name
+    pub fn values() -> [NamePart; 3] {
+        [
+            NamePart::LastName,
+            NamePart::FirstName,
+            NamePart::MiddleName,
+        ]
+    }
+}
+
+impl Display for NamePart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", &self.value())
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct FullName {
+    pub last_name: Option<String>,
+    pub first_name: Option<String>,
+    pub middle_name: Option<String>,
+}
+
+impl FullName {
+    //Empty parts are treated as missing
+    pub fn new(last_name: &str, first_name: &str, middle_name: &str) -> FullName {
+        FullName {
+            last_name: non_empty(last_name),
+            first_name: non_empty(first_name),
+            middle_name: non_empty(middle_name),
+        }
+    }
+
+    pub fn get(&self, part: &NamePart) -> Option<&str> {
+        match part {
+            NamePart::LastName => self.last_name.as_deref(),
+            NamePart::FirstName => self.first_name.as_deref(),
+            NamePart::MiddleName => self.middle_name.as_deref(),
+        }
+    }
+
+    pub fn set(&mut self, part: &NamePart, value: Option<String>) {
+        match part {
+            NamePart::LastName => self.last_name = value,
+            NamePart::FirstName => self.first_name = value,
+            NamePart::MiddleName => self.middle_name = value,
+        }
+    }
+
+    //Gender detected by the middle name
+    pub fn gender(&self) -> Gender {
+        match &self.middle_name {
+            None => Gender::Androgynous,
+            Some(middle_name) => Gender::detect_gender(middle_name),
+        }
+    }
+
+    //Applies `f` to every present part
+    pub fn map<F>(&self, f: F) -> FullName
+    where
+        F: Fn(&NamePart, &str) -> String,
+    {
+        let mut result = FullName::default();
+        for part in NamePart::values() {
+            if let Some(value) = self.get(&part) {
+                result.set(&part, Some(f(&part, value)));
+            }
+        }
+        result
+    }
+}
+
+impl Display for FullName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<&str> = NamePart::values()
+            .iter()
+            .filter_map(|part| self.get(part))
+            .collect();
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    let value = value.trim();
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
+
+//Copies the letter case of `original` to the lowercase `inflected` word.
+//Words written in capitals stay in capitals: "ИВАНОВ" -> "ИВАНОВУ".
+pub(crate) fn restore_case(original: &str, inflected: &str) -> String {
+    let original: Vec<&str> = original.trim().split('-').collect();
+    let mut result = String::with_capacity(inflected.len());
+    for (i, word) in inflected.split('-').enumerate() {
+        if i > 0 {
+            result.push('-');
+        }
+        let original = original.get(i).copied().unwrap_or_default();
+        if is_upper_case(original) {
+            result.push_str(&word.to_uppercase());
+            continue;
+        }
+        let mut original = original.chars();
+        for c in word.chars() {
+            match original.next() {
+                Some(o) if o.is_uppercase() => result.extend(c.to_uppercase()),
+                _ => result.push(c),
+            }
+        }
+    }
+    result
+}
+
+fn is_upper_case(word: &str) -> bool {
+    word.chars().filter(|c| c.is_alphabetic()).count() > 1
+        && word.chars().all(|c| !c.is_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_name_test() {
+        let name = FullName::new("Иванов", " ", "Петровна");
+        assert_eq!(Some("Иванов"), name.get(&NamePart::LastName));
+        assert_eq!(None, name.first_name);
+        assert_eq!(Gender::Female, name.gender());
+        assert_eq!("Иванов Петровна", name.to_string());
+        assert_eq!(Gender::Male, FullName::new("", "", "ИВАНОВИЧ").gender());
+    }
+
+    #[test]
+    fn restore_case_test() {
+        assert_eq!("Иванову", restore_case("Иванов", "иванову"));
+        assert_eq!("ИВАНОВУ", restore_case("ИВАНОВ", "иванову"));
+        assert_eq!(
+            "Римскому-Корсакову",
+            restore_case("Римский-Корсаков", "римскому-корсакову")
+        );
+        assert_eq!("льву", restore_case("лев", "льву"));
+        assert_eq!("Льву", restore_case("Лев", "льву"));
+        assert_eq!("Ю", restore_case("Ю", "ю"));
+    }
+}
@@ -0,0 +1,184 @@
+use std::borrow::Cow;
+use unicode_normalization::{is_nfc_quick, IsNormalized, UnicodeNormalization};
+
+//Normalization settings applied before rule matching.
+//Only the key used for matching is folded, the inflected output keeps the caller's spelling.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Normalization {
+    //compose decomposed characters (е + U+0308 -> ё)
+    pub nfc: bool,
+    //treat 'ё' and 'е' as the same letter
+    pub fold_yo: bool,
+    //replace Latin look-alike letters in words that contain Cyrillic letters
+    pub homoglyphs: bool,
+}
+
+impl Normalization {
+    pub fn none() -> Normalization {
+        Normalization {
+            nfc: false,
+            fold_yo: false,
+            homoglyphs: false,
+        }
+    }
+
+    //Trimmed and (optionally) composed name, used as a base for the output.
+    //Borrows the name if it is already composed.
+    pub fn prepare<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        let name = name.trim();
+        if self.nfc && is_nfc_quick(name.chars()) != IsNormalized::Yes {
+            Cow::Owned(name.nfc().collect())
+        } else {
+            Cow::Borrowed(name)
+        }
+    }
+
+    //Lowercased word with homoglyphs repaired and 'ё' folded, used to search rules
+    pub fn match_key(&self, word: &str) -> String {
+        let mut key = String::with_capacity(word.len());
+        self.push_match_key(word, &mut key);
+        key
+    }
+
+    pub(crate) fn push_match_key(&self, word: &str, out: &mut String) {
+        let repair = self.homoglyphs && word.chars().any(is_cyrillic);
+        for c in word.chars() {
+            let c = if repair { to_cyrillic(c) } else { c };
+            for c in c.to_lowercase() {
+                out.push(if self.fold_yo { fold_yo(c) } else { c });
+            }
+        }
+    }
+
+    //Whether the word is its own match key, so it can be matched without a copy
+    pub(crate) fn is_match_key(&self, word: &str) -> bool {
+        let repair = self.homoglyphs && word.chars().any(is_cyrillic);
+        word.chars().all(|c| {
+            is_lowercase(c)
+                && (!repair || to_cyrillic(c) == c)
+                && (!self.fold_yo || fold_yo(c) == c)
+        })
+    }
+}
+
+impl Default for Normalization {
+    fn default() -> Self {
+        Normalization {
+            nfc: true,
+            fold_yo: false,
+            homoglyphs: true,
+        }
+    }
+}
+
+//`str::ends_with` which treats 'ё' and 'е' as the same letter
+pub(crate) fn ends_with_folded(word: &str, suffix: &str) -> bool {
+    let mut word = word.chars().rev();
+    for s in suffix.chars().rev() {
+        match word.next() {
+            Some(w) if fold_yo(w) == fold_yo(s) => {}
+            _ => return false,
+        }
+    }
+    true
+}
+
+//Whether lowercasing keeps the char as is
+pub(crate) fn is_lowercase(c: char) -> bool {
+    let mut lowercase = c.to_lowercase();
+    lowercase.next() == Some(c) && lowercase.next().is_none()
+}
+
+fn fold_yo(c: char) -> char {
+    match c {
+        'ё' => 'е',
+        'Ё' => 'Е',
+        _ => c,
+    }
+}
+
+fn is_cyrillic(c: char) -> bool {
+    ('\u{0400}'..='\u{04FF}').contains(&c)
+}
+
+fn to_cyrillic(c: char) -> char {
+    match c {
+        'A' => 'А',
+        'B' => 'В',
+        'C' => 'С',
+        'E' => 'Е',
+        'H' => 'Н',
+        'K' => 'К',
+        'M' => 'М',
+        'O' => 'О',
+        'P' => 'Р',
+        'T' => 'Т',
+        'X' => 'Х',
+        'Y' => 'У',
+        'a' => 'а',
+        'c' => 'с',
+        'e' => 'е',
+        'k' => 'к',
+        'o' => 'о',
+        'p' => 'р',
+        'x' => 'х',
+        'y' => 'у',
+        _ => c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prepare_test() {
+        let normalization = Normalization::default();
+        assert_eq!("Пётр", normalization.prepare(" Пе\u{0308}тр "));
+        assert_eq!(
+            "пе\u{0308}тр",
+            Normalization::none().prepare("пе\u{0308}тр")
+        );
+    }
+
+    #[test]
+    fn match_key_test() {
+        let normalization = Normalization::default();
+        assert_eq!("иванов", normalization.match_key("Ивaнoв"));
+        assert_eq!("иванов", normalization.match_key("ИBAНОВ"));
+        assert_eq!("ivanov", normalization.match_key("Ivanov"));
+        assert_eq!("пётр", normalization.match_key("Пётр"));
+
+        let normalization = Normalization {
+            fold_yo: true,
+            ..Normalization::default()
+        };
+        assert_eq!("петр", normalization.match_key("ПЁТР"));
+
+        assert_eq!("ивaнoв", Normalization::none().match_key("Ивaнoв"));
+    }
+
+    #[test]
+    fn is_match_key_test() {
+        let normalization = Normalization::default();
+        assert!(normalization.is_match_key("иванов"));
+        assert!(normalization.is_match_key("пётр"));
+        assert!(normalization.is_match_key("ivanov"));
+        assert!(!normalization.is_match_key("Иванов"));
+        assert!(!normalization.is_match_key("ивaнoв"));
+        let normalization = Normalization {
+            fold_yo: true,
+            ..Normalization::default()
+        };
+        assert!(!normalization.is_match_key("пётр"));
+    }
+
+    #[test]
+    fn ends_with_folded_test() {
+        assert!(ends_with_folded("петр", "ётр"));
+        assert!(ends_with_folded("пётр", "етр"));
+        assert!(ends_with_folded("пётр", ""));
+        assert!(!ends_with_folded("тр", "етр"));
+        assert!(!ends_with_folded("петр", "ётл"));
+    }
+}
@@ -0,0 +1,37 @@
+use std::fmt;
+use std::fmt::Display;
+
+const SINGULAR: &str = "singular";
+const PLURAL: &str = "plural";
+
+//Grammar number constants
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Number {
+    //единственное
+    Singular,
+    //множественное
+    Plural,
+}
+
+impl Number {
+    pub fn value(&self) -> &'static str {
+        match self {
+            Number::Singular => SINGULAR,
+            Number::Plural => PLURAL,
+        }
+    }
+
+    pub fn of(number: &str) -> Option<Number> {
+        match number {
+            SINGULAR => Some(Number::Singular),
+            PLURAL => Some(Number::Plural),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", &self.value())
+    }
+}
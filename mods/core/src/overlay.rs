@@ -0,0 +1,374 @@
+use crate::case::Case;
+use crate::errors::PetrovichError;
+use crate::gender::Gender;
+use crate::name::NamePart;
+use crate::rules;
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+#[cfg(feature = "fs")]
+use std::fs;
+use yaml_rust::{Yaml, YamlLoader};
+
+const EXCEPTIONS: &str = "exceptions";
+
+//Cases of the forms of an override, the nominative case is the name itself
+const FORM_CASES: [Case; 5] = [
+    Case::Genitive,
+    Case::Dative,
+    Case::Accusative,
+    Case::Instrumental,
+    Case::Prepositional,
+];
+
+//User correction of a single word, applied before the rules of its name part
+#[derive(PartialEq, Debug, Clone)]
+pub struct Override {
+    part: NamePart,
+    gender: Gender,
+    name: String,
+    //genitive, dative, accusative, instrumental and prepositional forms
+    forms: [String; 5],
+}
+
+//User overrides kept separately from the rules file.
+//Saved in the rules file format as exceptions, so an overlay is a valid rules file.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct Overlay {
+    overrides: Vec<Override>,
+}
+
+impl Override {
+    //The name and the forms are lowercased as words in rules
+    pub fn new(
+        part: NamePart,
+        gender: Gender,
+        name: &str,
+        forms: [&str; 5],
+    ) -> Result<Override, PetrovichError> {
+        let name = to_word(name)?;
+        let mut result = Override {
+            part,
+            gender,
+            name,
+            forms: Default::default(),
+        };
+        for (form, value) in result.forms.iter_mut().zip(forms) {
+            *form = to_word(value)?;
+        }
+        Ok(result)
+    }
+
+    pub fn part(&self) -> NamePart {
+        self.part
+    }
+
+    pub fn gender(&self) -> Gender {
+        self.gender
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn form(&self, case: &Case) -> &str {
+        match FORM_CASES.iter().position(|form_case| form_case == case) {
+            Some(index) => &self.forms[index],
+            None => &self.name,
+        }
+    }
+
+    //Modifiers of the exception rule
+    pub(crate) fn mods(&self) -> Vec<String> {
+        self.forms
+            .iter()
+            .map(|form| modifier(&self.name, form))
+            .collect()
+    }
+
+    fn is_same_word(&self, other: &Override) -> bool {
+        self.part == other.part && self.gender == other.gender && self.name == other.name
+    }
+}
+
+impl Overlay {
+    pub fn load_from_str(content: &str) -> Result<Overlay, Box<dyn Error>> {
+        let yaml = YamlLoader::load_from_str(content)?;
+        let mut overlay = Overlay::default();
+        if let Some(yaml) = yaml.first() {
+            for part in NamePart::values() {
+                for o in overrides_of(&yaml[part.value()][EXCEPTIONS], part)? {
+                    overlay.add(o);
+                }
+            }
+        }
+        Ok(overlay)
+    }
+
+    #[cfg(feature = "fs")]
+    pub fn load_from_file(filename: &str) -> Result<Overlay, Box<dyn Error>> {
+        let content = fs::read_to_string(filename)?;
+        Overlay::load_from_str(&content)
+    }
+
+    pub fn to_yaml(&self) -> String {
+        let mut result = String::new();
+        for part in NamePart::values() {
+            let mut overrides = self.overrides.iter().filter(|o| o.part == part).peekable();
+            if overrides.peek().is_none() {
+                continue;
+            }
+            result.push_str(&format!("{}:\n  {}:\n", part, EXCEPTIONS));
+            for o in overrides {
+                let mods: Vec<String> = o.mods().iter().map(|m| quoted(m)).collect();
+                result.push_str(&format!(
+                    "    - gender: {}\n      test: [{}]\n      mods: [{}]\n",
+                    o.gender,
+                    quoted(&o.name),
+                    mods.join(", ")
+                ));
+            }
+        }
+        result
+    }
+
+    pub fn overrides(&self) -> &[Override] {
+        &self.overrides
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.overrides.is_empty()
+    }
+
+    //Replaces an override of the same word, part and gender
+    pub fn add(&mut self, o: Override) {
+        match self
+            .overrides
+            .iter_mut()
+            .find(|other| other.is_same_word(&o))
+        {
+            Some(other) => *other = o,
+            None => self.overrides.push(o),
+        }
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<Override> {
+        if index < self.overrides.len() {
+            Some(self.overrides.remove(index))
+        } else {
+            None
+        }
+    }
+
+    //Adds overrides of another overlay, e.g. an imported one
+    pub fn extend(&mut self, other: Overlay) {
+        for o in other.overrides {
+            self.add(o);
+        }
+    }
+}
+
+impl Display for Override {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} ({}): {}",
+            self.part,
+            self.name,
+            self.gender,
+            self.forms.join(", ")
+        )
+    }
+}
+
+// Only letters, so the words need no escaping in YAML
+fn to_word(value: &str) -> Result<String, PetrovichError> {
+    let word = value.trim().to_lowercase();
+    if word.is_empty() || !word.chars().all(char::is_alphabetic) {
+        return Err(PetrovichError::new(&format!(
+            "Override word '{}' must consist of letters only",
+            value.trim()
+        )));
+    }
+    Ok(word)
+}
+
+// Words like "null" or "true" are strings only when quoted
+fn quoted(value: &str) -> String {
+    format!("'{}'", value)
+}
+
+// The shortest modifier turning the word into the form: ("жук", "жуку") -> "у"
+fn modifier(word: &str, form: &str) -> String {
+    if word == form {
+        return ".".to_string();
+    }
+    let common = word
+        .chars()
+        .zip(form.chars())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let removed = word.chars().count() - common;
+    let added: String = form.chars().skip(common).collect();
+    format!("{}{}", "-".repeat(removed), added)
+}
+
+fn overrides_of(array: &Yaml, part: NamePart) -> Result<Vec<Override>, Box<dyn Error>> {
+    let path = format!("{}.{}", part, EXCEPTIONS);
+    let mut result = Vec::new();
+    for node in array.as_vec().map(Vec::as_slice).unwrap_or_default() {
+        let gender = node["gender"]
+            .as_str()
+            .and_then(Gender::of)
+            .ok_or_else(|| PetrovichError::new(&format!("Override without gender in {}", path)))?;
+        let mods: Vec<&str> = node["mods"]
+            .as_vec()
+            .map(|mods| mods.iter().filter_map(Yaml::as_str).collect())
+            .unwrap_or_default();
+        let tests = node["test"].as_vec().map(Vec::as_slice).unwrap_or_default();
+        for test in tests.iter().filter_map(Yaml::as_str) {
+            let forms = forms_of(test, &mods).ok_or_else(|| {
+                PetrovichError::new(&format!(
+                    "Override '{}' in {} must have five valid mods",
+                    test, path
+                ))
+            })?;
+            let forms = forms.each_ref().map(String::as_str);
+            result.push(Override::new(part, gender, test, forms)?);
+        }
+    }
+    Ok(result)
+}
+
+fn forms_of(word: &str, mods: &[&str]) -> Option<[String; 5]> {
+    let mods: &[&str; 5] = mods.try_into().ok()?;
+    let mut forms: [String; 5] = Default::default();
+    for (form, modifier) in forms.iter_mut().zip(mods) {
+        if !rules::is_valid_modifier(modifier) {
+            return None;
+        }
+        *form = word.to_string();
+        if !rules::apply_modifier_into(form, 0, modifier) {
+            return None;
+        }
+    }
+    Some(forms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zhuk() -> Override {
+        Override::new(
+            NamePart::LastName,
+            Gender::Female,
+            "Жук",
+            ["жук", "жук", "жук", "жук", "жук"],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn modifier_test() {
+        assert_eq!("у", modifier("жук", "жуку"));
+        assert_eq!("--ьва", modifier("лев", "льва"));
+        assert_eq!(".", modifier("жук", "жук"));
+        assert_eq!("-ой", modifier("петрова", "петровой"));
+    }
+
+    #[test]
+    fn yaml_test() {
+        let lev = Override::new(
+            NamePart::FirstName,
+            Gender::Male,
+            "Лев",
+            ["льва", "льву", "льва", "львом", "льве"],
+        )
+        .unwrap();
+        let mut overlay = Overlay::default();
+        overlay.add(lev.clone());
+        overlay.add(zhuk());
+        let expected = "\
+lastname:
+  exceptions:
+    - gender: female
+      test: ['жук']
+      mods: ['.', '.', '.', '.', '.']
+firstname:
+  exceptions:
+    - gender: male
+      test: ['лев']
+      mods: ['--ьва', '--ьву', '--ьва', '--ьвом', '--ьве']
+";
+        assert_eq!(expected, overlay.to_yaml());
+        let actual = Overlay::load_from_str(expected).unwrap();
+        assert_eq!(vec![zhuk(), lev.clone()], actual.overrides());
+        assert_eq!("львом", lev.form(&Case::Instrumental));
+        assert_eq!("лев", lev.form(&Case::Nominative));
+        assert_eq!(
+            "firstname лев (male): льва, льву, льва, львом, льве",
+            lev.to_string()
+        );
+    }
+
+    #[test]
+    fn keywords_test() {
+        let null = Override::new(
+            NamePart::LastName,
+            Gender::Male,
+            "Null",
+            ["nulla", "nullu", "nulla", "nullom", "nulle"],
+        )
+        .unwrap();
+        let truth =
+            Override::new(NamePart::FirstName, Gender::Female, "True", ["true"; 5]).unwrap();
+        let mut overlay = Overlay::default();
+        overlay.add(null);
+        overlay.add(truth);
+        let actual = Overlay::load_from_str(&overlay.to_yaml()).unwrap();
+        assert_eq!(overlay, actual);
+    }
+
+    #[test]
+    fn add_remove_test() {
+        let mut overlay = Overlay::default();
+        overlay.add(zhuk());
+        let forms = ["жука", "жуку", "жука", "жуком", "жуке"];
+        let male = Override::new(NamePart::LastName, Gender::Male, "жук", forms).unwrap();
+        overlay.add(male.clone());
+        let zhuka = Override::new(NamePart::LastName, Gender::Male, "жук", ["жука"; 5]).unwrap();
+        overlay.add(zhuka);
+        assert_eq!(2, overlay.overrides().len());
+        assert_eq!("жука", overlay.overrides()[1].form(&Case::Dative));
+        assert_eq!(Some(zhuk()), overlay.remove(0));
+        assert_eq!(None, overlay.remove(1));
+    }
+
+    #[test]
+    fn errors_test() {
+        let actual = Override::new(NamePart::LastName, Gender::Male, "Бонч-Бруевич", ["а"; 5]);
+        assert_eq!(
+            "Override word 'Бонч-Бруевич' must consist of letters only",
+            actual.unwrap_err().to_string()
+        );
+        let actual = Overlay::load_from_str(
+            "
+lastname:
+  exceptions:
+    - gender: male
+      test: [жук]
+      mods: [а, у]
+",
+        );
+        assert_eq!(
+            "Override 'жук' in lastname.exceptions must have five valid mods",
+            actual.unwrap_err().to_string()
+        );
+        let actual = Overlay::load_from_str("firstname:\n  exceptions:\n    - test: [лев]\n");
+        assert_eq!(
+            "Override without gender in firstname.exceptions",
+            actual.unwrap_err().to_string()
+        );
+    }
+}
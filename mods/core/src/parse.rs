@@ -0,0 +1,315 @@
+use crate::errors::PetrovichError;
+use crate::gender::Gender;
+use crate::name::{FullName, NamePart};
+use crate::normalize::Normalization;
+use crate::rules::RuleGroup;
+
+const MIDDLE_NAME_SUFFIXES: [&str; 6] = ["ич", "вна", "чна", "шна", "оглы", "кызы"];
+// Separate words of Turkic middle names: "Гусейн оглы"
+const MIDDLE_NAME_PARTICLES: [&str; 2] = ["оглы", "кызы"];
+const MALE_LAST_NAME_SUFFIXES: [&str; 8] = ["ов", "ев", "ёв", "ин", "ын", "ский", "цкий", "ской"];
+const FEMALE_LAST_NAME_SUFFIXES: [&str; 7] = ["ова", "ева", "ёва", "ина", "ына", "ская", "цкая"];
+const LAST_NAME_SUFFIXES: [&str; 6] = ["ых", "их", "енко", "ук", "юк", "швили"];
+
+// Candidates with lower confidence are not reported as alternatives.
+const MIN_CONFIDENCE: f64 = 0.01;
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct NameCandidate {
+    pub name: FullName,
+    pub gender: Gender,
+    //from 0 to 1, confidences of all candidates of a string sum up to 1
+    pub confidence: f64,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct ParsedName {
+    pub best: NameCandidate,
+    //other readings of the string, most probable first
+    pub alternatives: Vec<NameCandidate>,
+}
+
+struct Token {
+    value: String,
+    key: String,
+    initial: bool,
+}
+
+pub(crate) fn parse(
+    input: &str,
+    first_names: &RuleGroup,
+    normalization: &Normalization,
+) -> Result<ParsedName, PetrovichError> {
+    let tokens = tokenize(input, normalization);
+    if tokens.is_empty() {
+        return Err(PetrovichError::new("Name is empty"));
+    }
+    if tokens.len() > 3 {
+        return Err(PetrovichError::new(&format!(
+            "Name '{}' has more than three parts",
+            input.trim()
+        )));
+    }
+    let mut candidates: Vec<(Vec<NamePart>, f64)> = orders(tokens.len())
+        .into_iter()
+        .map(|order| {
+            let score = tokens
+                .iter()
+                .zip(&order)
+                .map(|(token, part)| token_score(token, part, first_names))
+                .product::<f64>()
+                * order_score(&order, &tokens);
+            (order, score)
+        })
+        .collect();
+    let total: f64 = candidates.iter().map(|(_, score)| score).sum();
+    candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+    let mut candidates = candidates
+        .into_iter()
+        .map(|(order, score)| to_candidate(&tokens, &order, score / total));
+    let best = candidates.next().unwrap();
+    let alternatives = candidates
+        .filter(|candidate| candidate.confidence >= MIN_CONFIDENCE)
+        .collect();
+    Ok(ParsedName { best, alternatives })
+}
+
+// "ИВАНОВ И.И." -> ["ИВАНОВ", "И.", "И."], "Гусейн оглы" is a single token
+fn tokenize(input: &str, normalization: &Normalization) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    for word in input.split_whitespace() {
+        let letters: Vec<&str> = word.split('.').filter(|s| !s.is_empty()).collect();
+        let initials = letters.iter().all(|s| s.chars().count() == 1)
+            && (word.contains('.') || word.chars().count() == 1);
+        if initials {
+            for letter in letters {
+                tokens.push(Token {
+                    value: format!("{}.", letter),
+                    key: normalization.match_key(letter),
+                    initial: true,
+                });
+            }
+        } else {
+            let value = normalization.prepare(word).into_owned();
+            let key = normalization.match_key(&value);
+            match tokens.last_mut() {
+                Some(last) if !last.initial && MIDDLE_NAME_PARTICLES.contains(&key.as_str()) => {
+                    last.value = format!("{} {}", last.value, value);
+                    last.key = format!("{} {}", last.key, key);
+                }
+                _ => tokens.push(Token {
+                    key,
+                    value,
+                    initial: false,
+                }),
+            }
+        }
+    }
+    tokens
+}
+
+// All assignments of name parts to `len` tokens
+fn orders(len: usize) -> Vec<Vec<NamePart>> {
+    let mut result = Vec::new();
+    for a in NamePart::values() {
+        if len == 1 {
+            result.push(vec![a]);
+            continue;
+        }
+        for b in NamePart::values().into_iter().filter(|b| *b != a) {
+            if len == 2 {
+                result.push(vec![a, b]);
+                continue;
+            }
+            for c in NamePart::values()
+                .into_iter()
+                .filter(|c| *c != a && *c != b)
+            {
+                result.push(vec![a, b, c]);
+            }
+        }
+    }
+    result
+}
+
+fn token_score(token: &Token, part: &NamePart, first_names: &RuleGroup) -> f64 {
+    if token.initial {
+        return match part {
+            NamePart::LastName => 0.01,
+            _ => 1.0,
+        };
+    }
+    let key = token.key.as_str();
+    let middle = ends_with_any(key, &MIDDLE_NAME_SUFFIXES);
+    let last = ends_with_any(key, &MALE_LAST_NAME_SUFFIXES)
+        || ends_with_any(key, &FEMALE_LAST_NAME_SUFFIXES)
+        || ends_with_any(key, &LAST_NAME_SUFFIXES);
+    let first = first_names.has_exception(key);
+    match part {
+        NamePart::MiddleName if middle => 1.0,
+        NamePart::MiddleName => 0.02,
+        _ if middle => 0.02,
+        // a known first name is a stronger sign than a suffix: "Лев"
+        NamePart::LastName if first => 0.2,
+        NamePart::LastName if last => 0.9,
+        NamePart::LastName => 0.3,
+        NamePart::FirstName if first => 0.9,
+        NamePart::FirstName if last => 0.15,
+        NamePart::FirstName => 0.5,
+    }
+}
+
+// How usual is the order of parts: "Иванов Иван Иванович" is the most common one
+fn order_score(order: &[NamePart], tokens: &[Token]) -> f64 {
+    use NamePart::*;
+    let first_initial = order
+        .iter()
+        .position(|part| *part == FirstName)
+        .is_some_and(|i| tokens[i].initial);
+    match order {
+        [LastName, FirstName, MiddleName] => 1.0,
+        [FirstName, MiddleName, LastName] => 0.6,
+        [LastName, FirstName] => 1.0,
+        [FirstName, LastName] if first_initial => 0.8,
+        [FirstName, LastName] => 0.6,
+        [FirstName, MiddleName] => 0.5,
+        [LastName] => 1.0,
+        [FirstName] => 0.8,
+        _ => 0.05,
+    }
+}
+
+fn to_candidate(tokens: &[Token], order: &[NamePart], confidence: f64) -> NameCandidate {
+    let mut name = FullName::default();
+    for (token, part) in tokens.iter().zip(order) {
+        name.set(part, Some(token.value.clone()));
+    }
+    let gender = detect_gender(tokens, order);
+    NameCandidate {
+        name,
+        gender,
+        confidence,
+    }
+}
+
+// By the middle name, then by the last name
+fn detect_gender(tokens: &[Token], order: &[NamePart]) -> Gender {
+    let key = |part: NamePart| {
+        order
+            .iter()
+            .position(|p| *p == part)
+            .map(|i| &tokens[i])
+            .filter(|token| !token.initial)
+            .map(|token| token.key.as_str())
+    };
+    if let Some(middle_name) = key(NamePart::MiddleName) {
+        let gender = Gender::detect_gender(middle_name);
+        if gender != Gender::Androgynous {
+            return gender;
+        }
+    }
+    match key(NamePart::LastName) {
+        Some(last_name) if ends_with_any(last_name, &FEMALE_LAST_NAME_SUFFIXES) => Gender::Female,
+        Some(last_name) if ends_with_any(last_name, &MALE_LAST_NAME_SUFFIXES) => Gender::Male,
+        _ => Gender::Androgynous,
+    }
+}
+
+fn ends_with_any(word: &str, suffixes: &[&str]) -> bool {
+    suffixes.iter().any(|suffix| word.ends_with(suffix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::Rules;
+
+    fn parse_name(input: &str) -> Result<ParsedName, PetrovichError> {
+        let rules = Rules::load_from_str(
+            "
+firstname:
+  exceptions:
+    - gender: male
+      test: [лев, пётр]
+      mods: [--ьва, --ьву, --ьва, --ьвом, --ьве]
+",
+        )
+        .unwrap();
+        parse(input, rules.first_name(), &Normalization::default())
+    }
+
+    fn best(input: &str) -> (FullName, Gender) {
+        let parsed = parse_name(input).unwrap();
+        (parsed.best.name, parsed.best.gender)
+    }
+
+    #[test]
+    fn parse_orders_test() {
+        let expected = FullName::new("Иванов", "Иван", "Иванович");
+        assert_eq!(
+            (expected.clone(), Gender::Male),
+            best("Иванов Иван Иванович")
+        );
+        assert_eq!((expected, Gender::Male), best(" Иван  Иванович Иванов "));
+        let expected = FullName::new("Петрова", "Анна", "");
+        assert_eq!((expected.clone(), Gender::Female), best("Петрова Анна"));
+        assert_eq!((expected, Gender::Female), best("Анна Петрова"));
+        let expected = FullName::new("", "Анна", "Сергеевна");
+        assert_eq!((expected, Gender::Female), best("Анна Сергеевна"));
+    }
+
+    #[test]
+    fn parse_particles_test() {
+        let expected = FullName::new("Алиев", "Рашид", "Гусейн оглы");
+        assert_eq!((expected, Gender::Male), best("Алиев Рашид Гусейн оглы"));
+        let expected = FullName::new("Алиева", "Лейла", "Гусейн кызы");
+        assert_eq!((expected, Gender::Female), best("Лейла Гусейн кызы Алиева"));
+    }
+
+    #[test]
+    fn parse_initials_test() {
+        let expected = FullName::new("ИВАНОВ", "И.", "И.");
+        assert_eq!((expected, Gender::Male), best("ИВАНОВ И.И."));
+        let expected = FullName::new("Петров", "П.", "С.");
+        assert_eq!((expected.clone(), Gender::Male), best("П. С. Петров"));
+        assert_eq!((expected, Gender::Male), best("Петров П.С"));
+    }
+
+    #[test]
+    fn parse_rules_test() {
+        // "Лев" is a first name exception of the rule set
+        let parsed = parse_name("Лев Толстой").unwrap();
+        assert_eq!(FullName::new("Толстой", "Лев", ""), parsed.best.name);
+        let parsed = parse_name("Толстой Лев").unwrap();
+        assert_eq!(FullName::new("Толстой", "Лев", ""), parsed.best.name);
+    }
+
+    #[test]
+    fn parse_confidence_test() {
+        let parsed = parse_name("Иванов Иван Иванович").unwrap();
+        assert!(parsed.best.confidence > 0.5);
+        assert!(parsed
+            .alternatives
+            .iter()
+            .all(|a| a.confidence <= parsed.best.confidence));
+
+        // no known suffixes, the order decides
+        let parsed = parse_name("Лебедь Олег").unwrap();
+        assert_eq!(FullName::new("Лебедь", "Олег", ""), parsed.best.name);
+        assert!(parsed.best.confidence < 0.7);
+        let alternative = &parsed.alternatives[0].name;
+        assert_eq!(&FullName::new("Олег", "Лебедь", ""), alternative);
+        let total: f64 = parsed.alternatives.iter().map(|a| a.confidence).sum();
+        assert!(total + parsed.best.confidence <= 1.0 + f64::EPSILON);
+    }
+
+    #[test]
+    fn parse_errors_test() {
+        assert_eq!("Name is empty", parse_name("  ").unwrap_err().to_string());
+        let actual = parse_name("Иванов Иван Иванович Младший").unwrap_err();
+        assert_eq!(
+            "Name 'Иванов Иван Иванович Младший' has more than three parts",
+            actual.to_string()
+        );
+    }
+}
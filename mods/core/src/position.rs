@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+use yaml_rust::parser::{MarkedEventReceiver, Parser};
+use yaml_rust::scanner::Marker;
+use yaml_rust::Event;
+
+// Lines of mappings which are items of sequences, by the path of the sequence:
+// "lastname.suffixes" -> lines of every rule of the section, starting from 1.
+// Columns are not kept, block mappings are marked at the first ':' by the parser.
+pub(crate) type Lines = HashMap<String, Vec<usize>>;
+
+pub(crate) fn rule_lines(content: &str) -> Lines {
+    let mut locator = Locator::default();
+    // The content is parsed by `YamlLoader` first, so errors are not expected here
+    let _ = Parser::new(content.chars()).load(&mut locator, false);
+    locator.lines
+}
+
+enum Frame {
+    // key of the value being parsed
+    Mapping { key: Option<String>, named: bool },
+    Sequence { named: bool },
+}
+
+#[derive(Default)]
+struct Locator {
+    frames: Vec<Frame>,
+    path: Vec<String>,
+    lines: Lines,
+}
+
+impl Locator {
+    fn start(&mut self, mapping: bool, mark: Marker) {
+        let name = match self.frames.last_mut() {
+            Some(Frame::Mapping { key: Some(key), .. }) => Some(key.clone()),
+            // complex keys are not used in rules files
+            Some(Frame::Mapping { key, .. }) => {
+                *key = Some(String::new());
+                None
+            }
+            Some(Frame::Sequence { .. }) if mapping => {
+                self.lines
+                    .entry(self.path.join("."))
+                    .or_default()
+                    .push(mark.line());
+                None
+            }
+            _ => None,
+        };
+        let named = name.is_some();
+        self.path.extend(name);
+        self.frames.push(if mapping {
+            Frame::Mapping { key: None, named }
+        } else {
+            Frame::Sequence { named }
+        });
+    }
+
+    fn end(&mut self) {
+        if let Some(Frame::Mapping { named: true, .. } | Frame::Sequence { named: true }) =
+            self.frames.pop()
+        {
+            self.path.pop();
+        }
+        self.value_parsed();
+    }
+
+    fn scalar(&mut self, value: String) {
+        match self.frames.last_mut() {
+            Some(Frame::Mapping {
+                key: key @ None, ..
+            }) => *key = Some(value),
+            _ => self.value_parsed(),
+        }
+    }
+
+    fn value_parsed(&mut self) {
+        if let Some(Frame::Mapping { key, .. }) = self.frames.last_mut() {
+            *key = None;
+        }
+    }
+}
+
+impl MarkedEventReceiver for Locator {
+    fn on_event(&mut self, event: Event, mark: Marker) {
+        match event {
+            Event::MappingStart(_) => self.start(true, mark),
+            Event::SequenceStart(_) => self.start(false, mark),
+            Event::MappingEnd | Event::SequenceEnd => self.end(),
+            Event::Scalar(value, ..) => self.scalar(value),
+            Event::Alias(_) => self.scalar(String::new()),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rule_lines_test() {
+        let lines = rule_lines(
+            "
+lastname:
+  exceptions:
+    - gender: male
+      test: [бонч]
+      mods: [., ., ., ., .]
+  suffixes:
+    - { gender: female, test: [ова], mods: [-ой, -ой, -у, -ой, -ой] }
+    -   gender: male
+        test:
+          - ов
+        mods: [а, у, а, ым, е]
+  forms:
+    vocative:
+      suffixes:
+        - gender: male
+          test: [ов]
+          mods: [.]
+firstname:
+  exceptions: []
+",
+        );
+        assert_eq!(Some(&vec![4]), lines.get("lastname.exceptions"));
+        assert_eq!(Some(&vec![8, 9]), lines.get("lastname.suffixes"));
+        assert_eq!(
+            Some(&vec![16]),
+            lines.get("lastname.forms.vocative.suffixes")
+        );
+        assert_eq!(None, lines.get("lastname.suffixes.test"));
+        assert_eq!(None, lines.get("firstname.exceptions"));
+    }
+}
@@ -1,15 +1,31 @@
 use crate::case::Case;
 use crate::errors::PetrovichError;
 use crate::gender::Gender;
+use crate::inflection::RuleInfo;
+use crate::name::NamePart;
+use crate::normalize;
+use crate::overlay::{Overlay, Override};
+use crate::position::{self, Lines};
+use std::collections::BTreeMap;
 use std::error::Error;
+#[cfg(feature = "fs")]
 use std::fs;
 use yaml_rust::{Yaml, YamlLoader};
 
+// Sections missing in a rules file are taken from here.
+const BUILTIN_RULES: &str = include_str!("builtin.yml");
+
+// Number of mods of the rules of name parts, named forms and plural last names
+const CASE_MODS: usize = 5;
+const FORM_MODS: usize = 1;
+const PLURAL_MODS: usize = 6;
+
 #[derive(Debug)]
 pub struct Rules {
     first_name: RuleGroup,
     last_name: RuleGroup,
     middle_name: RuleGroup,
+    last_name_plural: RuleGroup,
 }
 
 impl Rules {
@@ -22,126 +38,340 @@ impl Rules {
     pub fn middle_name(&self) -> &RuleGroup {
         &self.middle_name
     }
+    pub fn last_name_plural(&self) -> &RuleGroup {
+        &self.last_name_plural
+    }
+
+    // Every group with the number of mods its rules need
+    pub(crate) fn groups(&self) -> Vec<(&RuleGroup, usize)> {
+        let mut result = Vec::new();
+        for rule_group in [&self.last_name, &self.first_name, &self.middle_name] {
+            result.push((rule_group, CASE_MODS));
+            result.extend(rule_group.forms.values().map(|form| (form, FORM_MODS)));
+        }
+        result.push((&self.last_name_plural, PLURAL_MODS));
+        result
+    }
+
+    // Replaces overrides of the previous overlay
+    pub fn set_overlay(&mut self, overlay: &Overlay) {
+        for part in NamePart::values() {
+            let rule_group = match part {
+                NamePart::LastName => &mut self.last_name,
+                NamePart::FirstName => &mut self.first_name,
+                NamePart::MiddleName => &mut self.middle_name,
+            };
+            rule_group.overrides = overlay
+                .overrides()
+                .iter()
+                .filter(|o| o.part() == part)
+                .enumerate()
+                .map(|(index, o)| Rule::of_override(o, index))
+                .collect();
+        }
+    }
+    #[cfg(feature = "fs")]
     pub fn load_from_file(filename: &str) -> Result<Rules, Box<dyn Error>> {
         let content = fs::read_to_string(filename)?;
         Rules::load_from_str(&content)
     }
 
     pub fn load_from_str(content: &str) -> Result<Rules, Box<dyn Error>> {
-        let yaml = YamlLoader::load_from_str(&content)?;
-        let yaml = yaml.get(0);
-        match yaml {
-            None => Result::Err(PetrovichError::new("Can't parse yaml rules").into()),
-            Some(yaml) => Result::Ok(Rules::of(&yaml)),
+        let yaml = YamlLoader::load_from_str(content)?;
+        let builtin = YamlLoader::load_from_str(BUILTIN_RULES)?;
+        let lines = position::rule_lines(content);
+        match (yaml.first(), builtin.first()) {
+            (Some(yaml), Some(builtin)) => Result::Ok(Rules::of(yaml, builtin, &lines)?),
+            _ => Result::Err(PetrovichError::new("Can't parse yaml rules").into()),
         }
     }
 
-    fn of(rules: &Yaml) -> Rules {
-        let first_name = &rules["firstname"];
-        let last_name = &rules["lastname"];
-        let middle_name = &rules["middlename"];
-        Rules {
-            first_name: RuleGroup::of(&first_name),
-            last_name: RuleGroup::of(&last_name),
-            middle_name: RuleGroup::of(&middle_name),
-        }
+    // `lines` belong to `rules`, built-in rules have no line
+    fn of(rules: &Yaml, builtin: &Yaml, lines: &Lines) -> Result<Rules, PetrovichError> {
+        let last_name_plural = match section(rules, builtin, "lastname", "plural") {
+            (plural, true) => RuleGroup::of(plural, "lastname.plural", None)?,
+            (plural, false) => RuleGroup::of(plural, "lastname.plural", Some(lines))?,
+        };
+        Ok(Rules {
+            first_name: RuleGroup::of_part(rules, builtin, "firstname", lines)?,
+            last_name: RuleGroup::of_part(rules, builtin, "lastname", lines)?,
+            middle_name: RuleGroup::of_part(rules, builtin, "middlename", lines)?,
+            last_name_plural,
+        })
     }
 }
 
 #[derive(Debug)]
 pub struct RuleGroup {
+    // User overrides match whole words only and are checked first
+    overrides: Vec<Rule>,
     exceptions: Vec<Rule>,
     suffixes: Vec<Rule>,
+    // Additional named forms, every rule there has a single modifier.
+    forms: BTreeMap<String, RuleGroup>,
 }
 
 impl RuleGroup {
-    fn of(rules: &Yaml) -> RuleGroup {
-        let exceptions = to_rule_vec(&rules["exceptions"]);
-        let suffixes = to_rule_vec(&rules["suffixes"]);
-        RuleGroup {
+    // `path` names the section in error messages, e.g. "lastname.plural"
+    fn of(rules: &Yaml, path: &str, lines: Option<&Lines>) -> Result<RuleGroup, PetrovichError> {
+        let exceptions = to_rule_vec(&rules["exceptions"], &format!("{}.exceptions", path), lines)?;
+        let suffixes = to_rule_vec(&rules["suffixes"], &format!("{}.suffixes", path), lines)?;
+        Ok(RuleGroup {
+            overrides: Vec::new(),
             exceptions,
             suffixes,
+            forms: BTreeMap::new(),
+        })
+    }
+
+    // Forms of the rules file replace built-in forms with the same name.
+    fn of_part(
+        rules: &Yaml,
+        builtin: &Yaml,
+        part: &str,
+        lines: &Lines,
+    ) -> Result<RuleGroup, PetrovichError> {
+        let mut rule_group = RuleGroup::of(&rules[part], part, Some(lines))?;
+        let sections = [
+            (&builtin[part]["forms"], None),
+            (&rules[part]["forms"], Some(lines)),
+        ];
+        for (forms, lines) in sections {
+            if let Some(forms) = forms.as_hash() {
+                for (name, form) in forms {
+                    if let Some(name) = name.as_str() {
+                        let path = format!("{}.forms.{}", part, name);
+                        rule_group
+                            .forms
+                            .insert(name.to_string(), RuleGroup::of(form, &path, lines)?);
+                    }
+                }
+            }
         }
+        Ok(rule_group)
+    }
+
+    // Whether the word is listed in exceptions as a whole word
+    pub fn has_exception(&self, word: &str) -> bool {
+        self.overrides
+            .iter()
+            .chain(&self.exceptions)
+            .any(|rule| rule.test.iter().any(|test| test == word))
+    }
+
+    pub fn form(&self, name: &str) -> Option<&RuleGroup> {
+        self.forms.get(name)
+    }
+
+    // Exceptions followed by suffixes in the order they are tried, without overrides
+    pub(crate) fn rules(&self) -> impl Iterator<Item = &Rule> {
+        self.exceptions.iter().chain(&self.suffixes)
+    }
+
+    #[cfg(test)]
+    fn get_rule(&self, word: &str, gender: &Gender, first_word: bool) -> Option<&Rule> {
+        self.find(word, gender, first_word, false)
+            .map(|rule_match| rule_match.rule)
     }
 
-    pub fn get_rule(&self, word: &str, gender: &Gender, first_word: bool) -> Option<&Rule> {
-        let mut rule = RuleGroup::find_rule(&self.exceptions, word, gender, first_word);
+    // With `fold_yo` 'ё' and 'е' in rule tests are treated as the same letter.
+    pub fn find(
+        &self,
+        word: &str,
+        gender: &Gender,
+        first_word: bool,
+        fold_yo: bool,
+    ) -> Option<RuleMatch<'_>> {
+        let mut rule = RuleGroup::find_override(&self.overrides, word, gender, fold_yo);
         if rule.is_none() {
-            rule = RuleGroup::find_rule(&self.suffixes, word, gender, first_word);
+            rule = RuleGroup::find_rule(&self.exceptions, word, gender, first_word, fold_yo);
+        }
+        if rule.is_none() {
+            rule = RuleGroup::find_rule(&self.suffixes, word, gender, first_word, fold_yo).map(
+                |rule_match| RuleMatch {
+                    exception: false,
+                    ..rule_match
+                },
+            );
         }
         rule
     }
 
+    fn find_override<'a>(
+        rules: &'a [Rule],
+        word: &str,
+        gender: &Gender,
+        fold_yo: bool,
+    ) -> Option<RuleMatch<'a>> {
+        let len = word.chars().count();
+        rules.iter().find_map(|rule| {
+            let test = rule.matched_test(word, gender, false, fold_yo)?;
+            (test.chars().count() == len).then_some(RuleMatch {
+                rule,
+                test,
+                exception: true,
+            })
+        })
+    }
+
     fn find_rule<'a>(
         rules: &'a [Rule],
         word: &str,
         gender: &Gender,
         first_word: bool,
-    ) -> Option<&'a Rule> {
-        for rule in rules {
-            if rule.match_rule(word, gender, first_word) {
-                return Some(&rule);
-            }
-        }
-        None
+        fold_yo: bool,
+    ) -> Option<RuleMatch<'a>> {
+        rules.iter().find_map(|rule| {
+            let test = rule.matched_test(word, gender, first_word, fold_yo)?;
+            Some(RuleMatch {
+                rule,
+                test,
+                exception: true,
+            })
+        })
     }
 }
 
+// Rule found for a word together with the matched test string
+#[derive(PartialEq, Debug)]
+pub struct RuleMatch<'a> {
+    pub rule: &'a Rule,
+    pub test: &'a str,
+    pub exception: bool,
+}
+
 #[derive(PartialEq, Debug)]
 pub struct Rule {
     gender: Gender,
     test: Vec<String>,
     mods: Vec<String>,
     first_word: bool,
+    // Where the rule is defined: "lastname.suffixes", index in the section and line of the file
+    section: String,
+    index: usize,
+    line: Option<usize>,
 }
 
 impl Rule {
-    fn of(rules: &Yaml) -> Option<Rule> {
-        let gender = Gender::of(&rules["gender"].as_str()?)?;
+    fn of(
+        rules: &Yaml,
+        path: &str,
+        index: usize,
+        line: Option<usize>,
+    ) -> Result<Option<Rule>, PetrovichError> {
+        let gender = match rules["gender"].as_str().and_then(Gender::of) {
+            Some(gender) => gender,
+            None => return Ok(None),
+        };
         let test = to_str_vec(&rules["test"]);
         let mods = to_str_vec(&rules["mods"]);
         let first_word = !rules["tags"].is_badvalue();
+        if let Some(modifier) = mods.iter().find(|modifier| !is_valid_modifier(modifier)) {
+            return Err(PetrovichError::new(&format!(
+                "Malformed modifier '{}' of rule [{}] in {}",
+                modifier,
+                test.join(", "),
+                path
+            )));
+        }
 
         let rule = Rule {
             gender,
             test,
             mods,
             first_word,
+            section: path.to_string(),
+            index,
+            line,
         };
-        Some(rule)
+        Ok(Some(rule))
+    }
+
+    fn of_override(o: &Override, index: usize) -> Rule {
+        Rule {
+            gender: o.gender(),
+            test: vec![o.name().to_string()],
+            mods: o.mods(),
+            first_word: false,
+            section: format!("{}.overrides", o.part()),
+            index,
+            line: None,
+        }
     }
 
+    pub fn info(&self) -> RuleInfo {
+        RuleInfo {
+            gender: self.gender,
+            test: self.test.clone(),
+            mods: self.mods.clone(),
+            first_word: self.first_word,
+            section: self.section.clone(),
+            index: self.index,
+            line: self.line,
+        }
+    }
+
+    #[cfg(test)]
     fn match_rule(&self, word: &str, gender: &Gender, is_first_word: bool) -> bool {
+        self.matched_test(word, gender, is_first_word, false)
+            .is_some()
+    }
+
+    fn matched_test(
+        &self,
+        word: &str,
+        gender: &Gender,
+        is_first_word: bool,
+        fold_yo: bool,
+    ) -> Option<&str> {
         if !gender.equal(&self.gender) {
-            return false;
+            return None;
         };
         // If property `first_word = true`
         // that means we can apply that rule for first word only.
         // If property `first_word = false`
         // that means we can apply that rule for any word.
         if self.first_word && !is_first_word {
-            return false;
+            return None;
         }
-        for val in &self.test {
-            if word.ends_with(val) {
-                return true;
-            }
-        }
-        false
+        self.test
+            .iter()
+            .find(|val| {
+                if fold_yo {
+                    normalize::ends_with_folded(word, val)
+                } else {
+                    word.ends_with(val.as_str())
+                }
+            })
+            .map(String::as_str)
+    }
+
+    // Whether the rule keeps a word unchanged in every case
+    pub fn is_indeclinable(&self) -> bool {
+        !self.mods.is_empty() && self.mods.iter().all(|modifier| modifier == ".")
+    }
+
+    // Modifier of a rule of a named form group
+    pub fn get_form_modifier(&self) -> Option<&String> {
+        self.mods.first()
     }
 
-    pub fn apply(&self, word: &str, case: &Case) -> String {
-        let modifier = self.get_modifier(case);
-        match modifier {
-            None => word.to_string(),
-            Some(modifier) => apply_modifier(word, modifier),
+    // Modifier of a rule of a plural group, where `mods` start with the nominative case
+    pub fn get_plural_modifier(&self, case: &Case) -> Option<&String> {
+        match case {
+            Case::Nominative => self.mods.first(),
+            Case::Genitive => self.mods.get(1),
+            Case::Dative => self.mods.get(2),
+            Case::Accusative => self.mods.get(3),
+            Case::Instrumental => self.mods.get(4),
+            Case::Prepositional => self.mods.get(5),
         }
     }
 
-    fn get_modifier(&self, case: &Case) -> Option<&String> {
+    pub fn get_modifier(&self, case: &Case) -> Option<&String> {
         match case {
             Case::Nominative => Option::None,
-            Case::Genitive => self.mods.get(0),
+            Case::Genitive => self.mods.first(),
             Case::Dative => self.mods.get(1),
             Case::Accusative => self.mods.get(2),
             Case::Instrumental => self.mods.get(3),
@@ -150,20 +380,50 @@ impl Rule {
     }
 }
 
+#[cfg(test)]
 fn apply_modifier(name: &str, modifier: &str) -> String {
     let mut name = String::from(name);
-    let mut postfix = String::new();
-    modifier.chars().for_each(|c| match c {
-        '-' => {
-            name.pop();
-        }
-        '.' => {}
-        _ => postfix.push(c),
-    });
-    name.push_str(&postfix);
+    apply_modifier_into(&mut name, 0, modifier);
     name
 }
 
+// Applies the modifier to the word which starts at `start` in `out` without allocations.
+// Every leading '-' removes the last char of the word, the rest is appended, "." keeps the word.
+// Returns false and keeps the word if the modifier removes more chars than the word has,
+// the word is then left uninflected and reported as matched by no rule.
+pub fn apply_modifier_into(out: &mut String, start: usize, modifier: &str) -> bool {
+    let removed = removed_chars(modifier);
+    if removed > 0 {
+        // Chars are counted in `out`, as lowercasing may change the length of the word.
+        match out[start..].char_indices().rev().nth(removed - 1) {
+            Some((end, _)) => out.truncate(start + end),
+            None => return false,
+        }
+    }
+    out.extend(modifier[removed..].chars().filter(|c| *c != '.'));
+    true
+}
+
+// Number of chars a modifier removes from the end of a word
+fn removed_chars(modifier: &str) -> usize {
+    modifier.bytes().take_while(|b| *b == b'-').count()
+}
+
+// "." or '-' chars followed by letters: "--ьва"
+pub(crate) fn is_valid_modifier(modifier: &str) -> bool {
+    modifier == "." || !modifier.trim_start_matches('-').contains(['-', '.'])
+}
+
+// The section and whether it is built-in
+fn section<'a>(rules: &'a Yaml, builtin: &'a Yaml, part: &str, name: &str) -> (&'a Yaml, bool) {
+    let section = &rules[part][name];
+    if section.is_badvalue() {
+        (&builtin[part][name], true)
+    } else {
+        (section, false)
+    }
+}
+
 fn to_str_vec(array: &Yaml) -> Vec<String> {
     if !array.is_array() {
         return Vec::new();
@@ -177,17 +437,30 @@ fn to_str_vec(array: &Yaml) -> Vec<String> {
     result
 }
 
-fn to_rule_vec(array: &Yaml) -> Vec<Rule> {
+fn to_rule_vec(
+    array: &Yaml,
+    path: &str,
+    lines: Option<&Lines>,
+) -> Result<Vec<Rule>, PetrovichError> {
     if !array.is_array() {
-        return Vec::new();
+        return Ok(Vec::new());
     }
+    let mut lines = lines
+        .and_then(|lines| lines.get(path))
+        .into_iter()
+        .flatten();
     let mut result = Vec::new();
-    for node in array.as_vec().unwrap() {
-        if let Some(rule) = Rule::of(node) {
+    for (index, node) in array.as_vec().unwrap().iter().enumerate() {
+        // Lines are kept for mapping items only, other items are not rules
+        let line = match node {
+            Yaml::Hash(_) => lines.next().copied(),
+            _ => None,
+        };
+        if let Some(rule) = Rule::of(node, path, index, line)? {
             result.push(rule);
         }
     }
-    result
+    Ok(result)
 }
 
 #[cfg(test)]
@@ -199,12 +472,12 @@ mod tests {
         let rules = &test_rules();
 
         let rule_group = rules.first_name();
-        let expected = rule_group.exceptions.get(0).unwrap();
+        let expected = rule_group.exceptions.first().unwrap();
         let actual = rule_group.get_rule("лев", &Gender::Male, false);
         assert_eq!(Some(expected), actual);
 
         let rule_group = rules.middle_name();
-        let expected = rule_group.exceptions.get(0).unwrap();
+        let expected = rule_group.exceptions.first().unwrap();
         let actual = rule_group.get_rule("борух", &Gender::Male, true);
         assert_eq!(Some(expected), actual);
 
@@ -220,6 +493,9 @@ mod tests {
             test: vec!["кс".to_string(), "пс".to_string(), "мс".to_string()],
             mods: vec![],
             first_word: false,
+            section: String::new(),
+            index: 0,
+            line: None,
         };
         let case1 = "прокс";
         let case2 = "пс";
@@ -250,6 +526,9 @@ mod tests {
             test: vec!["а".to_string(), "е".to_string(), "и".to_string()],
             mods: vec![],
             first_word: true,
+            section: String::new(),
+            index: 0,
+            line: None,
         };
         let case1 = "сова";
         let case2 = "е";
@@ -275,10 +554,52 @@ mod tests {
         assert!(!rule.match_rule("мидвед", &Gender::Male, false));
         assert!(!rule.match_rule("мидвед", &Gender::Male, true));
     }
+    #[test]
+    fn find_test() {
+        let rules = test_rules();
+        let rule_group = rules.last_name();
+        let actual = rule_group.find("бонч", &Gender::Male, true, false).unwrap();
+        assert_eq!("бонч", actual.test);
+        assert!(actual.exception);
+        assert!(actual.rule.is_indeclinable());
+
+        let rule_group = rules.middle_name();
+        let actual = rule_group
+            .find("ильич", &Gender::Male, false, false)
+            .unwrap();
+        assert_eq!("ьич", actual.test);
+        assert!(!actual.exception);
+        assert!(!actual.rule.is_indeclinable());
+    }
+
+    #[test]
+    fn find_folded_test() {
+        let rules = Rules::load_from_str(
+            "
+firstname:
+  exceptions:
+    - gender: male
+      test: [пётр]
+      mods: [---етра, ---етру, ---етра, ---етром, ---етре]
+",
+        )
+        .unwrap();
+        let rule_group = rules.first_name();
+        let expected = rule_group.exceptions.first();
+        let get_rule_folded = |word| {
+            rule_group
+                .find(word, &Gender::Male, false, true)
+                .map(|rule_match| rule_match.rule)
+        };
+        assert_eq!(None, rule_group.get_rule("петр", &Gender::Male, false));
+        assert_eq!(expected, get_rule_folded("петр"));
+        assert_eq!(expected, get_rule_folded("пётр"));
+    }
+
     #[test]
     fn get_modifier_test() {
         let rules = test_rules();
-        let rule = rules.first_name().exceptions.get(0).unwrap();
+        let rule = rules.first_name().exceptions.first().unwrap();
 
         let actual = rule.get_modifier(&Case::Nominative);
         assert_eq!(None, actual);
@@ -293,6 +614,38 @@ mod tests {
         let actual = rule.get_modifier(&Case::Prepositional);
         assert_eq!(Some(&"--ьве".to_string()), actual);
     }
+    #[test]
+    fn last_name_plural_test() {
+        let rules = test_rules();
+        let rule_group = rules.last_name_plural();
+        let rule = rule_group.get_rule("иванов", &Gender::Male, false).unwrap();
+        let apply_plural = |word, case| {
+            let modifier = rule.get_plural_modifier(case).unwrap();
+            apply_modifier(word, modifier)
+        };
+        assert_eq!("ивановы", apply_plural("иванов", &Case::Nominative));
+        assert_eq!("ивановых", apply_plural("иванов", &Case::Genitive));
+        assert_eq!("ивановыми", apply_plural("иванов", &Case::Instrumental));
+        assert_eq!(None, rule_group.get_rule("иванов", &Gender::Female, false));
+
+        let rules = Rules::load_from_str(
+            "
+lastname:
+  plural:
+    suffixes:
+      - gender: androgynous
+        test: [дт]
+        mods: [ы, ов, ам, ов, ами, ах]
+",
+        )
+        .unwrap();
+        let rule_group = rules.last_name_plural();
+        let rule = rule_group.get_rule("шмидт", &Gender::Male, false).unwrap();
+        let modifier = rule.get_plural_modifier(&Case::Dative).unwrap();
+        assert_eq!("шмидтам", apply_modifier("шмидт", modifier));
+        assert_eq!(None, rule_group.get_rule("иванов", &Gender::Male, false));
+    }
+
     #[test]
     fn apply_modifier_test() {
         let actual = apply_modifier("Маша", "-и");
@@ -304,8 +657,103 @@ mod tests {
         let actual = apply_modifier("Маша", ".");
         assert_eq!("Маша", actual);
 
+        // the modifier is longer than the word
         let actual = apply_modifier("", "-и");
-        assert_eq!("и", actual);
+        assert_eq!("", actual);
+        let actual = apply_modifier("ли", "---ьва");
+        assert_eq!("ли", actual);
+    }
+
+    #[test]
+    fn apply_modifier_into_test() {
+        let mut out = String::from("лев-пётр");
+        apply_modifier_into(&mut out, "лев-".len(), "---етру");
+        assert_eq!("лев-петру", out);
+
+        // chars before `start` are never removed
+        let mut out = String::from("лев-");
+        assert!(!apply_modifier_into(&mut out, "лев-".len(), "--и"));
+        assert_eq!("лев-", out);
+
+        // lowercase "İ" is two chars long
+        let mut out = "İа".to_lowercase();
+        assert!(apply_modifier_into(&mut out, 0, "--ы"));
+        assert_eq!("iы", out);
+    }
+
+    #[test]
+    fn lines_test() {
+        let rules = Rules::load_from_str(
+            "
+lastname:
+  suffixes:
+    - ов
+    - gender: male
+      test: [ов]
+      mods: [а, у, а, ым, е]
+",
+        )
+        .unwrap();
+        let actual: Vec<(usize, Option<usize>)> = rules
+            .last_name()
+            .rules()
+            .map(|rule| (rule.index, rule.line))
+            .collect();
+        assert_eq!(vec![(1, Some(5))], actual);
+    }
+
+    #[test]
+    fn malformed_modifier_test() {
+        let load = |mods: &str| {
+            let content = format!(
+                "
+lastname:
+  suffixes:
+    - gender: male
+      test: [ов]
+      mods: {}
+",
+                mods
+            );
+            Rules::load_from_str(&content)
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        };
+        assert_eq!(Ok(()), load("[а, у, а, ым, е]"));
+        assert_eq!(Ok(()), load("[., ., -а, --ой, '']"));
+        assert_eq!(
+            Err("Malformed modifier 'а-' of rule [ов] in lastname.suffixes".to_string()),
+            load("[а-, у, а, ым, е]")
+        );
+        assert_eq!(
+            Err("Malformed modifier '-.' of rule [ов] in lastname.suffixes".to_string()),
+            load("[а, у, а, ым, -.]")
+        );
+    }
+
+    #[test]
+    fn exception_modifier_test() {
+        // Exceptions are matched as suffixes, so a modifier may be longer than the test,
+        // it is checked against the matched word when applied
+        let rules = Rules::load_from_str(
+            "
+lastname:
+  exceptions:
+    - gender: female
+      test: [ва]
+      mods: [---овой, ---овой, ---ову, ---овой, ---овой]
+",
+        )
+        .unwrap();
+        let rule_group = rules.last_name();
+        let modifier = rule_group
+            .get_rule("петрова", &Gender::Female, false)
+            .and_then(|rule| rule.get_modifier(&Case::Accusative))
+            .unwrap();
+        assert_eq!("петрову", apply_modifier("петрова", modifier));
+        let mut word = String::from("ва");
+        assert!(!apply_modifier_into(&mut word, 0, modifier));
+        assert_eq!("ва", word);
     }
 
     fn test_rules() -> Rules {
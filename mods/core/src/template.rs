@@ -0,0 +1,389 @@
+use crate::case::Case;
+use crate::errors::PetrovichError;
+use crate::format::{self, NameFormat, NameStyle};
+use crate::gender::Gender;
+use crate::name::{self, FullName, NamePart};
+use crate::Petrovich;
+
+const PERSON: &str = "person";
+const LAST_NAME: &str = "last_name";
+const FIRST_NAME: &str = "first_name";
+const MIDDLE_NAME: &str = "middle_name";
+const INITIAL: &str = "initial";
+const UPPER: &str = "upper";
+const LOWER: &str = "lower";
+
+const OPEN: &str = "{{";
+const CLOSE: &str = "}}";
+
+//Template with placeholders like `{{ last_name | genitive }}`.
+//Fields: `person`, `last_name`, `first_name`, `middle_name`.
+//Filters: case names, named forms of the rules file (e.g. `vocative`),
+//`initial`, `upper` and `lower`. Forms are checked against the rules when parsed.
+#[derive(PartialEq, Debug)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+#[derive(PartialEq, Debug)]
+enum Segment {
+    Text(String),
+    Placeholder(Placeholder),
+}
+
+#[derive(PartialEq, Debug)]
+struct Placeholder {
+    field: Field,
+    filters: Vec<Filter>,
+}
+
+#[derive(PartialEq, Debug)]
+enum Field {
+    Person,
+    Part(NamePart),
+}
+
+#[derive(PartialEq, Debug)]
+enum Filter {
+    Case(Case),
+    Form(String),
+    Initial,
+    Upper,
+    Lower,
+}
+
+//Value of a placeholder while filters are applied
+enum Value {
+    Name(FullName),
+    Text(String),
+}
+
+impl Template {
+    pub fn parse(template: &str, petrovich: &Petrovich) -> Result<Template, PetrovichError> {
+        let mut segments = Vec::new();
+        let mut rest = template;
+        while let Some(start) = rest.find(OPEN) {
+            if start > 0 {
+                segments.push(Segment::Text(rest[..start].to_string()));
+            }
+            let body = &rest[start + OPEN.len()..];
+            let end = body.find(CLOSE).ok_or_else(|| {
+                let position = template.len() - rest.len() + start;
+                PetrovichError::new(&format!(
+                    "Unclosed placeholder at position {}",
+                    template[..position].chars().count()
+                ))
+            })?;
+            let placeholder = Placeholder::parse(&body[..end], petrovich)?;
+            segments.push(Segment::Placeholder(placeholder));
+            rest = &body[end + CLOSE.len()..];
+        }
+        if !rest.is_empty() {
+            segments.push(Segment::Text(rest.to_string()));
+        }
+        Ok(Template { segments })
+    }
+
+    pub fn render(
+        &self,
+        petrovich: &Petrovich,
+        name: &FullName,
+        gender: &Gender,
+    ) -> Result<String, PetrovichError> {
+        let mut result = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Text(text) => result.push_str(text),
+                Segment::Placeholder(placeholder) => {
+                    result.push_str(&placeholder.render(petrovich, name, gender)?)
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl Placeholder {
+    fn parse(body: &str, petrovich: &Petrovich) -> Result<Placeholder, PetrovichError> {
+        let mut items = body.split('|').map(str::trim);
+        let field = items.next().unwrap_or_default();
+        let field = match field {
+            PERSON => Field::Person,
+            LAST_NAME => Field::Part(NamePart::LastName),
+            FIRST_NAME => Field::Part(NamePart::FirstName),
+            MIDDLE_NAME => Field::Part(NamePart::MiddleName),
+            _ => {
+                return Err(PetrovichError::new(&format!(
+                    "Unknown field '{}' in placeholder '{}'",
+                    field,
+                    body.trim()
+                )))
+            }
+        };
+        let mut filters: Vec<Filter> = Vec::new();
+        for filter in items {
+            let filter = match filter {
+                "" => {
+                    return Err(PetrovichError::new(&format!(
+                        "Empty filter in placeholder '{}'",
+                        body.trim()
+                    )))
+                }
+                INITIAL => Filter::Initial,
+                UPPER => Filter::Upper,
+                LOWER => Filter::Lower,
+                _ => match Case::of(filter) {
+                    Some(case) => Filter::Case(case),
+                    None if field.has_form(petrovich, filter) => Filter::Form(filter.to_string()),
+                    None => {
+                        return Err(PetrovichError::new(&format!(
+                            "Unknown filter '{}' in placeholder '{}'",
+                            filter,
+                            body.trim()
+                        )))
+                    }
+                },
+            };
+            // Inflected names can't be inflected again
+            let previous = filters.iter().find(|previous| previous.is_inflection());
+            if let (true, Some(previous)) = (filter.is_inflection(), previous) {
+                return Err(PetrovichError::new(&format!(
+                    "Filter '{}' can't follow '{}'",
+                    filter.value(),
+                    previous.value()
+                )));
+            }
+            filters.push(filter);
+        }
+        Ok(Placeholder { field, filters })
+    }
+
+    fn render(
+        &self,
+        petrovich: &Petrovich,
+        name: &FullName,
+        gender: &Gender,
+    ) -> Result<String, PetrovichError> {
+        let mut value = Value::Name(match &self.field {
+            Field::Person => name.clone(),
+            Field::Part(part) => {
+                let mut selected = FullName::default();
+                selected.set(part, name.get(part).map(str::to_string));
+                selected
+            }
+        });
+        for filter in &self.filters {
+            value = match (filter, value) {
+                (Filter::Case(case), Value::Name(name)) => {
+                    Value::Name(petrovich.full_name(&name, gender, case))
+                }
+                (Filter::Form(form), Value::Name(name)) => {
+                    Value::Name(self.inflect_form(petrovich, &name, gender, form)?)
+                }
+                (Filter::Initial, Value::Name(name)) => Value::Text(self.initials(&name)),
+                (Filter::Upper, value) => Value::Text(value.into_text().to_uppercase()),
+                (Filter::Lower, value) => Value::Text(value.into_text().to_lowercase()),
+                (filter, Value::Text(_)) => {
+                    return Err(PetrovichError::new(&format!(
+                        "Filter '{}' must precede 'initial', 'upper' and 'lower'",
+                        filter.value()
+                    )))
+                }
+            };
+        }
+        Ok(value.into_text())
+    }
+
+    fn inflect_form(
+        &self,
+        petrovich: &Petrovich,
+        name: &FullName,
+        gender: &Gender,
+        form: &str,
+    ) -> Result<FullName, PetrovichError> {
+        let mut result = FullName::default();
+        for part in NamePart::values() {
+            if let Some(value) = name.get(&part) {
+                let inflected = petrovich
+                    .inflect_form(&part, value, gender, form)
+                    .ok_or_else(|| {
+                        PetrovichError::new(&format!(
+                            "No '{}' form for {} in the rules",
+                            form, part
+                        ))
+                    })?;
+                result.set(&part, Some(name::restore_case(value, &inflected)));
+            }
+        }
+        Ok(result)
+    }
+
+    //`person | initial` keeps the last name: "Иванов И. П."
+    fn initials(&self, name: &FullName) -> String {
+        match &self.field {
+            Field::Person => name.format(&NameFormat::new(NameStyle::LastNameInitials)),
+            Field::Part(part) => format::initials(name.get(part).unwrap_or_default()),
+        }
+    }
+}
+
+impl Field {
+    //`person` accepts forms of any name part, parts without the form fail when rendered
+    fn has_form(&self, petrovich: &Petrovich, form: &str) -> bool {
+        let has_form = |part: &NamePart| petrovich.rule_group(part).form(form).is_some();
+        match self {
+            Field::Person => NamePart::values().iter().any(has_form),
+            Field::Part(part) => has_form(part),
+        }
+    }
+}
+
+impl Filter {
+    fn is_inflection(&self) -> bool {
+        matches!(self, Filter::Case(_) | Filter::Form(_))
+    }
+
+    fn value(&self) -> &str {
+        match self {
+            Filter::Case(case) => case.value(),
+            Filter::Form(form) => form,
+            Filter::Initial => INITIAL,
+            Filter::Upper => UPPER,
+            Filter::Lower => LOWER,
+        }
+    }
+}
+
+impl Value {
+    fn into_text(self) -> String {
+        match self {
+            Value::Name(name) => name.to_string(),
+            Value::Text(text) => text,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::normalize::Normalization;
+    use crate::rules::Rules;
+
+    fn test_petrovich() -> Petrovich {
+        let rules = Rules::load_from_str(
+            "
+lastname:
+  suffixes:
+    - gender: male
+      test: [ов]
+      mods: [а, у, а, ым, е]
+firstname:
+  suffixes:
+    - gender: male
+      test: [н]
+      mods: [а, у, а, ом, е]
+middlename:
+  suffixes:
+    - gender: male
+      test: [ич]
+      mods: [а, у, а, ем, е]
+",
+        )
+        .unwrap();
+        Petrovich {
+            rules,
+            normalization: Normalization::default(),
+        }
+    }
+
+    fn render(template: &str, name: &FullName) -> Result<String, PetrovichError> {
+        let petrovich = test_petrovich();
+        Template::parse(template, &petrovich)?.render(&petrovich, name, &name.gender())
+    }
+
+    #[test]
+    fn render_test() {
+        let name = FullName::new("Иванов", "Иван", "Петрович");
+        let actual = render("Выдано {{ person | dative }}", &name).unwrap();
+        assert_eq!("Выдано Иванову Ивану Петровичу", actual);
+        let actual = render(
+            "Справка для {{ last_name | genitive }} {{ first_name | initial }}",
+            &name,
+        );
+        assert_eq!("Справка для Иванова И.", actual.unwrap());
+        let actual = render("{{person|genitive|initial}}", &name).unwrap();
+        assert_eq!("Иванова И. П.", actual);
+        let actual = render("{{ last_name | upper }}: {{ first_name }}", &name).unwrap();
+        assert_eq!("ИВАНОВ: Иван", actual);
+        let actual = render(
+            "{{ first_name | vocative }}!",
+            &FullName::new("", "Саша", ""),
+        );
+        assert_eq!("Саш!", actual.unwrap());
+        let actual = render("Текст без полей", &name).unwrap();
+        assert_eq!("Текст без полей", actual);
+    }
+
+    #[test]
+    fn missing_parts_test() {
+        let name = FullName::new("Иванов", "Иван", "");
+        let actual = render("{{ person | dative }}.{{ middle_name }}", &name).unwrap();
+        assert_eq!("Иванову Ивану.", actual);
+    }
+
+    #[test]
+    fn errors_test() {
+        let name = FullName::new("Иванов", "Иван", "Петрович");
+        let actual = render("{{ surname | dative }}", &name).unwrap_err();
+        assert_eq!(
+            "Unknown field 'surname' in placeholder 'surname | dative'",
+            actual.to_string()
+        );
+        let actual = render("{{ lastname }}", &name).unwrap_err();
+        assert_eq!(
+            "Unknown field 'lastname' in placeholder 'lastname'",
+            actual.to_string()
+        );
+        let actual = render("Выдано {{ person | dative", &name).unwrap_err();
+        assert_eq!("Unclosed placeholder at position 7", actual.to_string());
+        let actual = render("{{ person | datve }}", &name).unwrap_err();
+        assert_eq!(
+            "Unknown filter 'datve' in placeholder 'person | datve'",
+            actual.to_string()
+        );
+        // The filter is checked even if the field is empty
+        let name = FullName::new("Иванов", "Иван", "");
+        let actual = render("{{ middle_name | datve }}", &name).unwrap_err();
+        assert_eq!(
+            "Unknown filter 'datve' in placeholder 'middle_name | datve'",
+            actual.to_string()
+        );
+        let actual = render("{{ last_name | vocative }}", &name).unwrap_err();
+        assert_eq!(
+            "Unknown filter 'vocative' in placeholder 'last_name | vocative'",
+            actual.to_string()
+        );
+        let actual = render("{{ person | vocative }}", &name).unwrap_err();
+        assert_eq!(
+            "No 'vocative' form for lastname in the rules",
+            actual.to_string()
+        );
+        let actual = render("{{ person | initial | dative }}", &name).unwrap_err();
+        assert_eq!(
+            "Filter 'dative' must precede 'initial', 'upper' and 'lower'",
+            actual.to_string()
+        );
+        let actual = render("{{ person | dative | genitive }}", &name).unwrap_err();
+        assert_eq!(
+            "Filter 'genitive' can't follow 'dative'",
+            actual.to_string()
+        );
+        let actual = render("{{ first_name | vocative | upper | dative }}", &name).unwrap_err();
+        assert_eq!(
+            "Filter 'dative' can't follow 'vocative'",
+            actual.to_string()
+        );
+        let actual = render("{{ person | }}", &name).unwrap_err();
+        assert_eq!("Empty filter in placeholder 'person |'", actual.to_string());
+    }
+}
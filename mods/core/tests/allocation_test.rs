@@ -0,0 +1,66 @@
+mod counting;
+
+use std::borrow::Cow;
+
+use counting::allocations;
+use petrovich_core::case::Case;
+use petrovich_core::gender::Gender;
+use petrovich_core::name::NamePart;
+use petrovich_core::Petrovich;
+
+const RULES: &str = "
+lastname:
+  exceptions:
+    - gender: androgynous
+      test: [бонч]
+      mods: [., ., ., ., .]
+      tags: [first_word]
+  suffixes:
+    - gender: male
+      test: [ов, ев, ин]
+      mods: [а, у, а, ым, е]
+    - gender: androgynous
+      test: [о, и, ых]
+      mods: [., ., ., ., .]
+";
+
+#[test]
+fn inflect_into_allocation_test() {
+    let petrovich: Petrovich = RULES.parse().unwrap();
+    let mut out = String::with_capacity(64);
+    for name in ["Иванов", "Бонч-Бруевич", "ПЕТРОВ", "Ивaнoв", "Смит"]
+    {
+        for case in [Case::Genitive, Case::Instrumental] {
+            let count = allocations(|| {
+                out.clear();
+                petrovich.inflect_into(&NamePart::LastName, name, &Gender::Male, &case, &mut out);
+            });
+            assert_eq!(0, count, "'{}' in {} allocates", name, case);
+        }
+    }
+    assert!(
+        allocations(|| {
+            petrovich.last_name("Иванов", &Gender::Male, &Case::Genitive);
+        }) > 0
+    );
+}
+
+#[test]
+fn inflect_cow_allocation_test() {
+    let petrovich: Petrovich = RULES.parse().unwrap();
+    let part = &NamePart::LastName;
+    for (name, case) in [
+        ("иванов", Case::Nominative),
+        ("черных", Case::Dative),
+        ("бонч-бруевич", Case::Nominative),
+        ("смит", Case::Genitive),
+    ] {
+        let count = allocations(|| {
+            let cow = petrovich.inflect_cow(part, name, &Gender::Male, &case);
+            assert!(matches!(cow, Cow::Borrowed(_)));
+        });
+        assert_eq!(0, count, "'{}' in {} allocates", name, case);
+    }
+    let cow = petrovich.inflect_cow(part, "иванов", &Gender::Male, &Case::Dative);
+    assert_eq!("иванову", cow);
+}
@@ -4,11 +4,18 @@ use std::path::Path;
 
 use petrovich_core::case::Case;
 use petrovich_core::gender::Gender;
+use petrovich_core::number::Number;
+#[cfg(feature = "fs")]
 use petrovich_core::Petrovich;
 
+// The rules file and petrovich-eval are not used by the plural test
+#[allow(dead_code)]
 const RULES_FILE: &str = "petrovich-rules/rules.yml";
+#[allow(dead_code)]
 const TEST_FILE_DIR: &str = "petrovich-eval/";
 
+#[allow(dead_code)]
+#[cfg(feature = "fs")]
 pub fn load_petrovich() -> Petrovich {
     Petrovich::new(RULES_FILE).unwrap_or_else(|error| panic!("Problem reading rules: {:?}", error))
 }
@@ -26,10 +33,18 @@ pub struct NameTestCase {
     pub expected: String,
     pub gender: Gender,
     pub case: Case,
+    // read by the plural test only
+    #[allow(dead_code)]
+    pub number: Number,
 }
 
+#[allow(dead_code)]
 pub fn load_name_test_cases(file_name: &str) -> Vec<NameTestCase> {
-    read_lines(format!("{}{}", TEST_FILE_DIR, file_name))
+    load_test_cases(&format!("{}{}", TEST_FILE_DIR, file_name))
+}
+
+pub fn load_test_cases(path: &str) -> Vec<NameTestCase> {
+    read_lines(path)
         .skip(1)
         .map(|line| map_2_name_test_case(&line.unwrap()))
         .collect()
@@ -37,16 +52,18 @@ pub fn load_name_test_cases(file_name: &str) -> Vec<NameTestCase> {
 
 fn map_2_name_test_case(line: &str) -> NameTestCase {
     let split: Vec<&str> = line.split('\t').collect();
-    let name = split.get(0).unwrap().to_string();
+    let name = split.first().unwrap().to_string();
     let expected = split.get(1).unwrap().to_lowercase();
     let split: Vec<&str> = split.get(2).unwrap().split(',').collect();
-    let gender = get_gender(split.get(0).unwrap());
+    let gender = get_gender(split.first().unwrap());
+    let number = get_number(split.get(1).unwrap());
     let case = get_case(split.get(2).unwrap());
     NameTestCase {
         name,
         expected,
         gender,
         case,
+        number,
     }
 }
 
@@ -58,6 +75,13 @@ fn get_gender(gender: &str) -> Gender {
     }
 }
 
+fn get_number(number: &str) -> Number {
+    match number {
+        "мн" => Number::Plural,
+        _ => Number::Singular,
+    }
+}
+
 fn get_case(gender: &str) -> Case {
     match gender {
         "рд" => Case::Genitive,
@@ -0,0 +1,17 @@
+mod common;
+
+use petrovich_core::Petrovich;
+
+// Plural rules are built in, so the rules file is not needed
+const RULES: &str = "{}";
+
+#[test]
+fn last_name_plural_test() {
+    let petrovich: Petrovich = RULES.parse().unwrap();
+    let test_cases = common::load_test_cases("tests/data/lastnames.plural.tsv");
+    for test in test_cases {
+        let actual =
+            petrovich.last_name_with_number(&test.name, &test.gender, &test.case, &test.number);
+        common::examine_answer(actual, &test.expected)
+    }
+}
@@ -0,0 +1,133 @@
+use petrovich_core::case::Case;
+use petrovich_core::gender::Gender;
+use petrovich_core::name::{FullName, NamePart};
+use petrovich_core::normalize::Normalization;
+use petrovich_core::Petrovich;
+use proptest::prelude::*;
+
+const RULES: &str = "
+firstname:
+  exceptions:
+    - gender: male
+      test: [лев, пётр]
+      mods: [--ьва, --ьву, --ьва, --ьвом, --ьве]
+  suffixes:
+    - gender: female
+      test: [а]
+      mods: [-ы, -е, -у, -ой, -е]
+lastname:
+  exceptions:
+    - gender: androgynous
+      test: [бонч]
+      mods: [., ., ., ., .]
+      tags: [first_word]
+  suffixes:
+    - gender: male
+      test: [ов, ин]
+      mods: [а, у, а, ым, е]
+    - gender: androgynous
+      test: [ых, о]
+      mods: [., ., ., ., .]
+    - gender: androgynous
+      test: [ь, i̇]
+      mods: [---я, ---ю, ---я, ---ем, ---е]
+middlename:
+  suffixes:
+    - gender: male
+      test: [ич]
+      mods: [а, у, а, ем, е]
+";
+
+fn cases() -> [Case; 6] {
+    [
+        Case::Nominative,
+        Case::Genitive,
+        Case::Dative,
+        Case::Accusative,
+        Case::Instrumental,
+        Case::Prepositional,
+    ]
+}
+
+fn petrovich(normalization: Normalization) -> Petrovich {
+    let mut petrovich: Petrovich = RULES.parse().unwrap();
+    petrovich.set_normalization(normalization);
+    petrovich
+}
+
+fn normalization() -> impl Strategy<Value = Normalization> {
+    (any::<bool>(), any::<bool>(), any::<bool>()).prop_map(|(nfc, fold_yo, homoglyphs)| {
+        Normalization {
+            nfc,
+            fold_yo,
+            homoglyphs,
+        }
+    })
+}
+
+fn gender() -> impl Strategy<Value = Gender> {
+    prop_oneof![
+        Just(Gender::Male),
+        Just(Gender::Female),
+        Just(Gender::Androgynous)
+    ]
+}
+
+// Arbitrary Unicode mixed with letters and hyphens the rules care about
+fn name() -> impl Strategy<Value = String> {
+    prop_oneof![
+        any::<String>(),
+        "[-а-яёА-ЯЁa-zİΣ\u{0308} ]{0,24}",
+        "(лев|пётр|бонч|ов|ь|İ|-|Σ|e\u{0308})*",
+    ]
+}
+
+proptest! {
+    #[test]
+    fn inflect_apis_agree(
+        name in name(),
+        gender in gender(),
+        normalization in normalization(),
+    ) {
+        let petrovich = petrovich(normalization);
+        for part in NamePart::values() {
+            for case in cases() {
+                let expected = petrovich.inflect(&part, &name, &gender, &case);
+                let mut out = String::from("prefix");
+                petrovich.inflect_into(&part, &name, &gender, &case, &mut out);
+                prop_assert_eq!(format!("prefix{}", expected), out);
+                prop_assert_eq!(&expected, &petrovich.inflect_cow(&part, &name, &gender, &case));
+                let detailed = petrovich.inflect_detailed(&part, &name, &gender, &case);
+                prop_assert_eq!(&expected, &detailed.value);
+                prop_assert_eq!(
+                    name.trim().matches('-').count(),
+                    expected.matches('-').count()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn other_apis_do_not_panic(
+        name in name(),
+        gender in gender(),
+        normalization in normalization(),
+    ) {
+        let petrovich = petrovich(normalization);
+        for case in cases() {
+            let full_name = FullName::new(&name, &name, &name);
+            petrovich.full_name(&full_name, &gender, &case);
+        }
+        for part in NamePart::values() {
+            petrovich.inflect_form(&part, &name, &gender, "vocative");
+        }
+        let _ = petrovich.parse_name(&name);
+    }
+
+    #[test]
+    fn rules_do_not_panic(content in any::<String>()) {
+        if let Ok(petrovich) = content.parse::<Petrovich>() {
+            petrovich.last_name("Иванов", &Gender::Male, &Case::Genitive);
+        }
+    }
+}
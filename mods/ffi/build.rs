@@ -0,0 +1,6 @@
+use std::env;
+
+// The C API test compiles a C program for the same target with the cc crate
+fn main() {
+    println!("cargo:rustc-env=TARGET={}", env::var("TARGET").unwrap());
+}
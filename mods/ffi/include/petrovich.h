@@ -0,0 +1,104 @@
+/*
+ * C API of petrovich, inflection of Russian names.
+ *
+ * Functions return PETROVICH_OK or an error code,
+ * petrovich_last_error() describes the last error of the calling thread.
+ * Strings are NUL-terminated UTF-8. Results are written to a caller buffer,
+ * `len` receives the result length in bytes without NUL. If the buffer is too small
+ * PETROVICH_ERROR_BUFFER_TOO_SMALL is returned and `len` receives the required length.
+ *
+ * Pointers must be NULL or valid. An instance may be used from several threads at once,
+ * it must be freed with petrovich_free() exactly once.
+ */
+
+#ifndef PETROVICH_H
+#define PETROVICH_H
+
+/* Generated by cbindgen from mods/ffi/src/lib.rs, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#define PETROVICH_OK 0
+
+#define PETROVICH_ERROR_NULL_POINTER 1
+
+#define PETROVICH_ERROR_INVALID_UTF8 2
+
+#define PETROVICH_ERROR_INVALID_ARGUMENT 3
+
+#define PETROVICH_ERROR_RULES 4
+
+#define PETROVICH_ERROR_BUFFER_TOO_SMALL 5
+
+#define PETROVICH_ERROR_UNSUPPORTED 6
+
+#define PETROVICH_ERROR_PANIC 7
+
+#define PETROVICH_PART_LASTNAME 0
+
+#define PETROVICH_PART_FIRSTNAME 1
+
+#define PETROVICH_PART_MIDDLENAME 2
+
+#define PETROVICH_GENDER_MALE 0
+
+#define PETROVICH_GENDER_FEMALE 1
+
+#define PETROVICH_GENDER_ANDROGYNOUS 2
+
+#define PETROVICH_CASE_NOMINATIVE 0
+
+#define PETROVICH_CASE_GENITIVE 1
+
+#define PETROVICH_CASE_DATIVE 2
+
+#define PETROVICH_CASE_ACCUSATIVE 3
+
+#define PETROVICH_CASE_INSTRUMENTAL 4
+
+#define PETROVICH_CASE_PREPOSITIONAL 5
+
+typedef struct Petrovich Petrovich;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+int petrovich_new_embedded(struct Petrovich **petrovich);
+
+int petrovich_new_from_file(const char *path, struct Petrovich **petrovich);
+
+int petrovich_new_from_str(const char *rules, struct Petrovich **petrovich);
+
+void petrovich_free(struct Petrovich *petrovich);
+
+int petrovich_inflect(const struct Petrovich *petrovich,
+                      int part,
+                      const char *name,
+                      int gender,
+                      int case_,
+                      char *buf,
+                      size_t buf_len,
+                      size_t *len);
+
+int petrovich_inflect_full_name(const struct Petrovich *petrovich,
+                                const char *last_name,
+                                const char *first_name,
+                                const char *middle_name,
+                                int gender,
+                                int case_,
+                                char *buf,
+                                size_t buf_len,
+                                size_t *len);
+
+const char *petrovich_last_error(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* PETROVICH_H */
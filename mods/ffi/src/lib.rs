@@ -0,0 +1,310 @@
+// Pointer requirements of the C API are described in the header
+#![allow(clippy::missing_safety_doc, clippy::too_many_arguments)]
+
+use petrovich_core::case::Case;
+use petrovich_core::gender::Gender;
+use petrovich_core::name::{FullName, NamePart};
+use std::cell::RefCell;
+use std::ffi::{c_char, c_int, CStr, CString};
+use std::panic::{self, UnwindSafe};
+use std::ptr;
+
+pub const PETROVICH_OK: c_int = 0;
+pub const PETROVICH_ERROR_NULL_POINTER: c_int = 1;
+pub const PETROVICH_ERROR_INVALID_UTF8: c_int = 2;
+pub const PETROVICH_ERROR_INVALID_ARGUMENT: c_int = 3;
+pub const PETROVICH_ERROR_RULES: c_int = 4;
+pub const PETROVICH_ERROR_BUFFER_TOO_SMALL: c_int = 5;
+pub const PETROVICH_ERROR_UNSUPPORTED: c_int = 6;
+pub const PETROVICH_ERROR_PANIC: c_int = 7;
+
+pub const PETROVICH_PART_LASTNAME: c_int = 0;
+pub const PETROVICH_PART_FIRSTNAME: c_int = 1;
+pub const PETROVICH_PART_MIDDLENAME: c_int = 2;
+
+pub const PETROVICH_GENDER_MALE: c_int = 0;
+pub const PETROVICH_GENDER_FEMALE: c_int = 1;
+pub const PETROVICH_GENDER_ANDROGYNOUS: c_int = 2;
+
+pub const PETROVICH_CASE_NOMINATIVE: c_int = 0;
+pub const PETROVICH_CASE_GENITIVE: c_int = 1;
+pub const PETROVICH_CASE_DATIVE: c_int = 2;
+pub const PETROVICH_CASE_ACCUSATIVE: c_int = 3;
+pub const PETROVICH_CASE_INSTRUMENTAL: c_int = 4;
+pub const PETROVICH_CASE_PREPOSITIONAL: c_int = 5;
+
+//Opaque handle for C callers
+pub struct Petrovich {
+    inner: petrovich_core::Petrovich,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+//Error code with a message for `petrovich_last_error`
+struct FfiError {
+    code: c_int,
+    message: String,
+}
+
+impl FfiError {
+    fn new(code: c_int, message: &str) -> FfiError {
+        FfiError {
+            code,
+            message: message.to_string(),
+        }
+    }
+}
+
+type FfiResult<T> = Result<T, FfiError>;
+
+//Runs `f` catching panics, so they never unwind into C code
+fn call<F: FnOnce() -> FfiResult<()> + UnwindSafe>(f: F) -> c_int {
+    let result = panic::catch_unwind(f)
+        .unwrap_or_else(|_| Err(FfiError::new(PETROVICH_ERROR_PANIC, "Internal error")));
+    match result {
+        Ok(()) => {
+            set_last_error(None);
+            PETROVICH_OK
+        }
+        Err(error) => {
+            set_last_error(Some(&error.message));
+            error.code
+        }
+    }
+}
+
+fn set_last_error(message: Option<&str>) {
+    // Messages never contain NUL, but a message is better lost than a panic
+    let message = message.and_then(|message| CString::new(message).ok());
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = message);
+}
+
+unsafe fn to_str<'a>(value: *const c_char, name: &str) -> FfiResult<&'a str> {
+    if value.is_null() {
+        return Err(FfiError::new(
+            PETROVICH_ERROR_NULL_POINTER,
+            &format!("'{}' is NULL", name),
+        ));
+    }
+    CStr::from_ptr(value).to_str().map_err(|_| {
+        FfiError::new(
+            PETROVICH_ERROR_INVALID_UTF8,
+            &format!("'{}' is not valid UTF-8", name),
+        )
+    })
+}
+
+//NULL is an empty part
+unsafe fn to_optional_str<'a>(value: *const c_char, name: &str) -> FfiResult<&'a str> {
+    if value.is_null() {
+        Ok("")
+    } else {
+        to_str(value, name)
+    }
+}
+
+unsafe fn to_petrovich<'a>(petrovich: *const Petrovich) -> FfiResult<&'a Petrovich> {
+    petrovich
+        .as_ref()
+        .ok_or_else(|| FfiError::new(PETROVICH_ERROR_NULL_POINTER, "'petrovich' is NULL"))
+}
+
+fn to_part(part: c_int) -> FfiResult<NamePart> {
+    match part {
+        PETROVICH_PART_LASTNAME => Ok(NamePart::LastName),
+        PETROVICH_PART_FIRSTNAME => Ok(NamePart::FirstName),
+        PETROVICH_PART_MIDDLENAME => Ok(NamePart::MiddleName),
+        _ => Err(invalid_argument("part", part)),
+    }
+}
+
+fn to_gender(gender: c_int) -> FfiResult<Gender> {
+    match gender {
+        PETROVICH_GENDER_MALE => Ok(Gender::Male),
+        PETROVICH_GENDER_FEMALE => Ok(Gender::Female),
+        PETROVICH_GENDER_ANDROGYNOUS => Ok(Gender::Androgynous),
+        _ => Err(invalid_argument("gender", gender)),
+    }
+}
+
+fn to_case(case: c_int) -> FfiResult<Case> {
+    match case {
+        PETROVICH_CASE_NOMINATIVE => Ok(Case::Nominative),
+        PETROVICH_CASE_GENITIVE => Ok(Case::Genitive),
+        PETROVICH_CASE_DATIVE => Ok(Case::Dative),
+        PETROVICH_CASE_ACCUSATIVE => Ok(Case::Accusative),
+        PETROVICH_CASE_INSTRUMENTAL => Ok(Case::Instrumental),
+        PETROVICH_CASE_PREPOSITIONAL => Ok(Case::Prepositional),
+        _ => Err(invalid_argument("case", case)),
+    }
+}
+
+fn invalid_argument(name: &str, value: c_int) -> FfiError {
+    FfiError::new(
+        PETROVICH_ERROR_INVALID_ARGUMENT,
+        &format!("Unknown {} {}", name, value),
+    )
+}
+
+//Copies `value` with a terminating NUL to the caller buffer
+unsafe fn write_result(
+    value: &str,
+    buf: *mut c_char,
+    buf_len: usize,
+    len: *mut usize,
+) -> FfiResult<()> {
+    if !len.is_null() {
+        *len = value.len();
+    }
+    if buf.is_null() || buf_len <= value.len() {
+        return Err(FfiError::new(
+            PETROVICH_ERROR_BUFFER_TOO_SMALL,
+            &format!("Buffer of {} bytes is too small", buf_len),
+        ));
+    }
+    ptr::copy_nonoverlapping(value.as_ptr(), buf as *mut u8, value.len());
+    *buf.add(value.len()) = 0;
+    Ok(())
+}
+
+unsafe fn create(
+    petrovich: *mut *mut Petrovich,
+    inner: petrovich_core::Petrovich,
+) -> FfiResult<()> {
+    *petrovich = Box::into_raw(Box::new(Petrovich { inner }));
+    Ok(())
+}
+
+fn check_out(petrovich: *mut *mut Petrovich) -> FfiResult<()> {
+    if petrovich.is_null() {
+        Err(FfiError::new(
+            PETROVICH_ERROR_NULL_POINTER,
+            "'petrovich' is NULL",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+fn rules_error(error: Box<dyn std::error::Error>) -> FfiError {
+    FfiError::new(PETROVICH_ERROR_RULES, &error.to_string())
+}
+
+//Creates an instance with rules compiled into the library.
+//Returns PETROVICH_ERROR_UNSUPPORTED if the library is built without them.
+#[no_mangle]
+pub unsafe extern "C" fn petrovich_new_embedded(petrovich: *mut *mut Petrovich) -> c_int {
+    call(|| {
+        check_out(petrovich)?;
+        #[cfg(feature = "embedded-rules")]
+        {
+            let inner = petrovich_core::Petrovich::embedded().map_err(rules_error)?;
+            create(petrovich, inner)
+        }
+        #[cfg(not(feature = "embedded-rules"))]
+        Err(FfiError::new(
+            PETROVICH_ERROR_UNSUPPORTED,
+            "The library is built without embedded rules",
+        ))
+    })
+}
+
+//Creates an instance with rules of a YAML file
+#[no_mangle]
+pub unsafe extern "C" fn petrovich_new_from_file(
+    path: *const c_char,
+    petrovich: *mut *mut Petrovich,
+) -> c_int {
+    call(|| {
+        check_out(petrovich)?;
+        let path = to_str(path, "path")?;
+        let inner = petrovich_core::Petrovich::new(path).map_err(rules_error)?;
+        create(petrovich, inner)
+    })
+}
+
+//Creates an instance with rules of a YAML string
+#[no_mangle]
+pub unsafe extern "C" fn petrovich_new_from_str(
+    rules: *const c_char,
+    petrovich: *mut *mut Petrovich,
+) -> c_int {
+    call(|| {
+        check_out(petrovich)?;
+        let inner = to_str(rules, "rules")?.parse().map_err(rules_error)?;
+        create(petrovich, inner)
+    })
+}
+
+//Frees an instance, NULL is ignored
+#[no_mangle]
+pub unsafe extern "C" fn petrovich_free(petrovich: *mut Petrovich) {
+    if !petrovich.is_null() {
+        drop(Box::from_raw(petrovich));
+    }
+}
+
+//Inflects a single name part, the result is lowercase
+#[no_mangle]
+pub unsafe extern "C" fn petrovich_inflect(
+    petrovich: *const Petrovich,
+    part: c_int,
+    name: *const c_char,
+    gender: c_int,
+    case: c_int,
+    buf: *mut c_char,
+    buf_len: usize,
+    len: *mut usize,
+) -> c_int {
+    call(|| {
+        let petrovich = to_petrovich(petrovich)?;
+        let part = to_part(part)?;
+        let name = to_str(name, "name")?;
+        let result = petrovich
+            .inner
+            .inflect(&part, name, &to_gender(gender)?, &to_case(case)?);
+        write_result(&result, buf, buf_len, len)
+    })
+}
+
+//Inflects a full name keeping the letter case, parts are joined with spaces.
+//NULL or empty parts are skipped.
+#[no_mangle]
+pub unsafe extern "C" fn petrovich_inflect_full_name(
+    petrovich: *const Petrovich,
+    last_name: *const c_char,
+    first_name: *const c_char,
+    middle_name: *const c_char,
+    gender: c_int,
+    case: c_int,
+    buf: *mut c_char,
+    buf_len: usize,
+    len: *mut usize,
+) -> c_int {
+    call(|| {
+        let petrovich = to_petrovich(petrovich)?;
+        let name = FullName::new(
+            to_optional_str(last_name, "last_name")?,
+            to_optional_str(first_name, "first_name")?,
+            to_optional_str(middle_name, "middle_name")?,
+        );
+        let result = petrovich
+            .inner
+            .full_name(&name, &to_gender(gender)?, &to_case(case)?);
+        write_result(&result.to_string(), buf, buf_len, len)
+    })
+}
+
+//Message of the last error of the calling thread or NULL.
+//Valid until the next call of the library in the thread.
+#[no_mangle]
+pub extern "C" fn petrovich_last_error() -> *const c_char {
+    LAST_ERROR.with(|last_error| {
+        last_error
+            .borrow()
+            .as_ref()
+            .map_or(ptr::null(), |message| message.as_ptr())
+    })
+}
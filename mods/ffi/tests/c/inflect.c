@@ -0,0 +1,29 @@
+/* Inflects a last name given by argv[2] with the rules of argv[1] into the dative case */
+
+#include <stdio.h>
+
+#include "petrovich.h"
+
+int main(int argc, char **argv) {
+    if (argc != 3) {
+        fprintf(stderr, "usage: inflect RULES NAME\n");
+        return 2;
+    }
+    struct Petrovich *petrovich = NULL;
+    if (petrovich_new_from_str(argv[1], &petrovich) != PETROVICH_OK) {
+        fprintf(stderr, "%s\n", petrovich_last_error());
+        return 1;
+    }
+    char buf[64];
+    size_t len = 0;
+    int status = petrovich_inflect(petrovich, PETROVICH_PART_LASTNAME, argv[2],
+                                   PETROVICH_GENDER_MALE, PETROVICH_CASE_DATIVE, buf,
+                                   sizeof(buf), &len);
+    if (status == PETROVICH_OK) {
+        printf("%s %zu\n", buf, len);
+    } else {
+        fprintf(stderr, "%s\n", petrovich_last_error());
+    }
+    petrovich_free(petrovich);
+    return status;
+}
@@ -0,0 +1,285 @@
+use petrovich_ffi::*;
+use std::env;
+use std::ffi::{c_char, c_int, CStr, CString};
+use std::path::Path;
+use std::process::Command;
+use std::ptr;
+
+const RULES: &str = "
+firstname:
+  suffixes:
+    - gender: male
+      test: [н]
+      mods: [а, у, а, ом, е]
+lastname:
+  suffixes:
+    - gender: male
+      test: [ов]
+      mods: [а, у, а, ым, е]
+middlename:
+  suffixes:
+    - gender: male
+      test: [ич]
+      mods: [а, у, а, ем, е]
+";
+
+fn new_petrovich() -> *mut Petrovich {
+    let rules = CString::new(RULES).unwrap();
+    let mut petrovich = ptr::null_mut();
+    let status = unsafe { petrovich_new_from_str(rules.as_ptr(), &mut petrovich) };
+    assert_eq!(PETROVICH_OK, status);
+    assert!(!petrovich.is_null());
+    petrovich
+}
+
+fn last_error() -> String {
+    let message = petrovich_last_error();
+    assert!(!message.is_null());
+    unsafe { CStr::from_ptr(message) }
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+fn inflect(petrovich: *const Petrovich, part: c_int, name: &str, case: c_int) -> String {
+    let name = CString::new(name).unwrap();
+    let mut buf = [0 as c_char; 64];
+    let mut len = 0;
+    let status = unsafe {
+        petrovich_inflect(
+            petrovich,
+            part,
+            name.as_ptr(),
+            PETROVICH_GENDER_MALE,
+            case,
+            buf.as_mut_ptr(),
+            buf.len(),
+            &mut len,
+        )
+    };
+    assert_eq!(PETROVICH_OK, status);
+    let result = unsafe { CStr::from_ptr(buf.as_ptr()) };
+    assert_eq!(len, result.to_bytes().len());
+    result.to_str().unwrap().to_string()
+}
+
+#[test]
+fn inflect_test() {
+    let petrovich = new_petrovich();
+    let actual = inflect(
+        petrovich,
+        PETROVICH_PART_LASTNAME,
+        "Иванов",
+        PETROVICH_CASE_DATIVE,
+    );
+    assert_eq!("иванову", actual);
+    let actual = inflect(
+        petrovich,
+        PETROVICH_PART_FIRSTNAME,
+        "Иван",
+        PETROVICH_CASE_INSTRUMENTAL,
+    );
+    assert_eq!("иваном", actual);
+    assert!(petrovich_last_error().is_null());
+    unsafe { petrovich_free(petrovich) };
+}
+
+#[test]
+fn inflect_full_name_test() {
+    let petrovich = new_petrovich();
+    let last_name = CString::new("Иванов").unwrap();
+    let first_name = CString::new("Иван").unwrap();
+    let mut buf = [0 as c_char; 64];
+    let mut len = 0;
+    let status = unsafe {
+        petrovich_inflect_full_name(
+            petrovich,
+            last_name.as_ptr(),
+            first_name.as_ptr(),
+            ptr::null(),
+            PETROVICH_GENDER_MALE,
+            PETROVICH_CASE_GENITIVE,
+            buf.as_mut_ptr(),
+            buf.len(),
+            &mut len,
+        )
+    };
+    assert_eq!(PETROVICH_OK, status);
+    let actual = unsafe { CStr::from_ptr(buf.as_ptr()) };
+    assert_eq!("Иванова Ивана", actual.to_str().unwrap());
+    unsafe { petrovich_free(petrovich) };
+}
+
+#[test]
+fn buffer_too_small_test() {
+    let petrovich = new_petrovich();
+    let name = CString::new("Иванов").unwrap();
+    let mut buf = [0 as c_char; 14];
+    let mut len = 0;
+    let mut call = |buf_len| unsafe {
+        petrovich_inflect(
+            petrovich,
+            PETROVICH_PART_LASTNAME,
+            name.as_ptr(),
+            PETROVICH_GENDER_MALE,
+            PETROVICH_CASE_DATIVE,
+            buf.as_mut_ptr(),
+            buf_len,
+            &mut len,
+        )
+    };
+    // "иванову" takes 14 bytes, one more is needed for NUL
+    assert_eq!(PETROVICH_ERROR_BUFFER_TOO_SMALL, call(14));
+    assert_eq!(14, len);
+    assert_eq!("Buffer of 14 bytes is too small", last_error());
+
+    // the length can be requested without a buffer
+    let status = unsafe {
+        petrovich_inflect(
+            petrovich,
+            PETROVICH_PART_LASTNAME,
+            name.as_ptr(),
+            PETROVICH_GENDER_MALE,
+            PETROVICH_CASE_DATIVE,
+            ptr::null_mut(),
+            0,
+            &mut len,
+        )
+    };
+    assert_eq!(PETROVICH_ERROR_BUFFER_TOO_SMALL, status);
+    assert_eq!(14, len);
+    unsafe { petrovich_free(petrovich) };
+}
+
+#[test]
+fn errors_test() {
+    let petrovich = new_petrovich();
+    let name = CString::new("Иванов").unwrap();
+    let mut buf = [0 as c_char; 64];
+    let mut len = 0;
+    let status = unsafe {
+        petrovich_inflect(
+            petrovich,
+            PETROVICH_PART_LASTNAME,
+            name.as_ptr(),
+            PETROVICH_GENDER_MALE,
+            42,
+            buf.as_mut_ptr(),
+            buf.len(),
+            &mut len,
+        )
+    };
+    assert_eq!(PETROVICH_ERROR_INVALID_ARGUMENT, status);
+    assert_eq!("Unknown case 42", last_error());
+
+    let invalid = [0xd0_u8, 0];
+    let status = unsafe {
+        petrovich_inflect(
+            petrovich,
+            PETROVICH_PART_LASTNAME,
+            invalid.as_ptr() as *const c_char,
+            PETROVICH_GENDER_MALE,
+            PETROVICH_CASE_DATIVE,
+            buf.as_mut_ptr(),
+            buf.len(),
+            &mut len,
+        )
+    };
+    assert_eq!(PETROVICH_ERROR_INVALID_UTF8, status);
+    assert_eq!("'name' is not valid UTF-8", last_error());
+
+    let status = unsafe {
+        petrovich_inflect(
+            ptr::null(),
+            PETROVICH_PART_LASTNAME,
+            name.as_ptr(),
+            PETROVICH_GENDER_MALE,
+            PETROVICH_CASE_DATIVE,
+            buf.as_mut_ptr(),
+            buf.len(),
+            &mut len,
+        )
+    };
+    assert_eq!(PETROVICH_ERROR_NULL_POINTER, status);
+    assert_eq!("'petrovich' is NULL", last_error());
+    unsafe { petrovich_free(petrovich) };
+}
+
+#[test]
+fn rules_errors_test() {
+    let mut petrovich = ptr::null_mut();
+    let rules = CString::new("lastname: [").unwrap();
+    let status = unsafe { petrovich_new_from_str(rules.as_ptr(), &mut petrovich) };
+    assert_eq!(PETROVICH_ERROR_RULES, status);
+    assert!(petrovich.is_null());
+    assert!(!last_error().is_empty());
+
+    let path = CString::new("missing/rules.yml").unwrap();
+    let status = unsafe { petrovich_new_from_file(path.as_ptr(), &mut petrovich) };
+    assert_eq!(PETROVICH_ERROR_RULES, status);
+    assert!(petrovich.is_null());
+
+    #[cfg(not(feature = "embedded-rules"))]
+    {
+        let status = unsafe { petrovich_new_embedded(&mut petrovich) };
+        assert_eq!(PETROVICH_ERROR_UNSUPPORTED, status);
+    }
+    // freeing NULL is allowed
+    unsafe { petrovich_free(ptr::null_mut()) };
+}
+
+// Compiles tests/c/inflect.c against include/petrovich.h and links it with the shared library
+#[cfg(unix)]
+#[test]
+fn c_program_test() {
+    // `cargo test` builds the rlib only, so the shared library is built here
+    let status = Command::new(env!("CARGO"))
+        .args(["build", "--lib", "--manifest-path"])
+        .arg(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml"))
+        .args(if cfg!(debug_assertions) {
+            &[][..]
+        } else {
+            &["--release"][..]
+        })
+        .status()
+        .unwrap();
+    assert!(status.success(), "Failed to build the library");
+    let out_dir = Path::new(env!("CARGO_TARGET_TMPDIR"));
+    // The test binary is in target/<profile>/deps, the library in target/<profile>
+    let exe = env::current_exe().unwrap();
+    let lib_dir = exe.parent().and_then(Path::parent).unwrap();
+    let compiler = cc::Build::new()
+        .target(env!("TARGET"))
+        .host(env!("TARGET"))
+        .opt_level(0)
+        .cargo_metadata(false)
+        .warnings_into_errors(true)
+        .include(concat!(env!("CARGO_MANIFEST_DIR"), "/include"))
+        .get_compiler();
+    let program = out_dir.join("petrovich_inflect");
+    let status = compiler
+        .to_command()
+        .arg(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/c/inflect.c"))
+        .arg("-o")
+        .arg(&program)
+        .arg(format!("-L{}", lib_dir.display()))
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()))
+        .arg("-lpetrovich_ffi")
+        .status()
+        .unwrap();
+    assert!(status.success(), "Failed to compile tests/c/inflect.c");
+
+    let output = Command::new(&program)
+        .args([RULES, "Иванов"])
+        .output()
+        .unwrap();
+    assert_eq!("", String::from_utf8(output.stderr).unwrap());
+    assert_eq!("иванову 14\n", String::from_utf8(output.stdout).unwrap());
+    assert!(output.status.success());
+    let output = Command::new(&program)
+        .args(["lastname: [", "Иванов"])
+        .output()
+        .unwrap();
+    assert!(!output.stderr.is_empty());
+    assert_eq!(Some(1), output.status.code());
+}
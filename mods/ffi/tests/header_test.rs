@@ -0,0 +1,25 @@
+use std::env;
+use std::fs;
+
+const HEADER: &str = "include/petrovich.h";
+
+// Set PETROVICH_UPDATE_HEADER=1 to regenerate the header after changes of the C API
+#[test]
+fn header_test() {
+    let crate_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let config = cbindgen::Config::from_root_or_default(&crate_dir);
+    let mut actual = Vec::new();
+    cbindgen::generate_with_config(&crate_dir, config)
+        .expect("Unable to generate the header")
+        .write(&mut actual);
+    let actual = String::from_utf8(actual).unwrap();
+    if env::var_os("PETROVICH_UPDATE_HEADER").is_some() {
+        fs::write(HEADER, &actual).unwrap();
+    }
+    let expected = fs::read_to_string(HEADER).unwrap_or_default();
+    assert!(
+        expected == actual,
+        "{} is outdated, run the test with PETROVICH_UPDATE_HEADER=1",
+        HEADER
+    );
+}
@@ -0,0 +1,12 @@
+use std::error::Error;
+use std::path::Path;
+
+// protoc is vendored, so no system protobuf compiler is needed
+fn main() -> Result<(), Box<dyn Error>> {
+    let protoc = protoc_bin_vendored::protoc_bin_path()?;
+    std::env::set_var("PROTOC", protoc);
+    let proto = Path::new("proto/petrovich.proto");
+    tonic_prost_build::compile_protos(proto)?;
+    println!("cargo:rerun-if-changed={}", proto.display());
+    Ok(())
+}
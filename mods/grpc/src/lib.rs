@@ -0,0 +1,224 @@
+use petrovich_core::case::Case;
+use petrovich_core::gender::Gender;
+use petrovich_core::name::{FullName, NamePart};
+use petrovich_core::Petrovich;
+use proto::inflect_result;
+use proto::petrovich_server::PetrovichServer;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio_stream::{Stream, StreamExt};
+use tonic::{Request, Response, Status, Streaming};
+
+pub mod proto {
+    tonic::include_proto!("petrovich.v1");
+}
+
+const CASES: [proto::Case; 6] = [
+    proto::Case::Nominative,
+    proto::Case::Genitive,
+    proto::Case::Dative,
+    proto::Case::Accusative,
+    proto::Case::Instrumental,
+    proto::Case::Prepositional,
+];
+
+// Number of names in a batch request
+pub const DEFAULT_MAX_BATCH_ITEMS: usize = 1000;
+
+type InflectResultStream = Pin<Box<dyn Stream<Item = Result<proto::InflectResult, Status>> + Send>>;
+
+pub struct PetrovichService {
+    petrovich: Arc<Petrovich>,
+    max_batch_items: usize,
+}
+
+impl PetrovichService {
+    pub fn new(petrovich: Arc<Petrovich>, max_batch_items: usize) -> PetrovichService {
+        PetrovichService {
+            petrovich,
+            max_batch_items,
+        }
+    }
+
+    pub fn into_server(self) -> PetrovichServer<PetrovichService> {
+        PetrovichServer::new(self)
+    }
+}
+
+#[tonic::async_trait]
+impl proto::petrovich_server::Petrovich for PetrovichService {
+    async fn inflect(
+        &self,
+        request: Request<proto::InflectRequest>,
+    ) -> Result<Response<proto::InflectResponse>, Status> {
+        let result = inflect_one(&self.petrovich, &request.into_inner())?;
+        Ok(Response::new(proto::InflectResponse { result }))
+    }
+
+    async fn inflect_full_name(
+        &self,
+        request: Request<proto::InflectFullNameRequest>,
+    ) -> Result<Response<proto::FullName>, Status> {
+        let request = request.into_inner();
+        let name = to_full_name(request.name);
+        let gender = to_name_gender(request.gender, &name)?;
+        let case = to_case(request.case)?;
+        let result = self.petrovich.full_name(&name, &gender, &case);
+        Ok(Response::new(to_proto_name(result)))
+    }
+
+    async fn declension(
+        &self,
+        request: Request<proto::DeclensionRequest>,
+    ) -> Result<Response<proto::DeclensionResponse>, Status> {
+        let request = request.into_inner();
+        let name = to_full_name(request.name);
+        let gender = to_name_gender(request.gender, &name)?;
+        let mut forms = Vec::with_capacity(CASES.len());
+        for case in CASES {
+            let inflected = self
+                .petrovich
+                .full_name(&name, &gender, &to_case(case as i32)?);
+            forms.push(proto::CaseForm {
+                case: case as i32,
+                name: Some(to_proto_name(inflected)),
+            });
+        }
+        Ok(Response::new(proto::DeclensionResponse { forms }))
+    }
+
+    async fn detect_gender(
+        &self,
+        request: Request<proto::DetectGenderRequest>,
+    ) -> Result<Response<proto::DetectGenderResponse>, Status> {
+        let middle_name = request.into_inner().middle_name;
+        let gender = to_proto_gender(Gender::detect_gender(&middle_name));
+        Ok(Response::new(proto::DetectGenderResponse {
+            gender: gender as i32,
+        }))
+    }
+
+    async fn batch_inflect(
+        &self,
+        request: Request<proto::BatchInflectRequest>,
+    ) -> Result<Response<proto::BatchInflectResponse>, Status> {
+        let requests = request.into_inner().requests;
+        if requests.len() > self.max_batch_items {
+            return Err(Status::resource_exhausted(format!(
+                "Batch of {} names exceeds the limit of {}",
+                requests.len(),
+                self.max_batch_items
+            )));
+        }
+        let results = requests
+            .iter()
+            .map(|request| to_result(inflect_one(&self.petrovich, request)))
+            .collect();
+        Ok(Response::new(proto::BatchInflectResponse { results }))
+    }
+
+    type StreamInflectStream = InflectResultStream;
+
+    async fn stream_inflect(
+        &self,
+        request: Request<Streaming<proto::InflectRequest>>,
+    ) -> Result<Response<Self::StreamInflectStream>, Status> {
+        let petrovich = Arc::clone(&self.petrovich);
+        let stream = request.into_inner().map(move |request| {
+            let request = request?;
+            Ok(to_result(inflect_one(&petrovich, &request)))
+        });
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+fn inflect_one(petrovich: &Petrovich, request: &proto::InflectRequest) -> Result<String, Status> {
+    let part = to_part(request.part)?;
+    let gender = match proto::Gender::try_from(request.gender) {
+        Ok(proto::Gender::Unspecified) => Err(Status::invalid_argument("Gender is not specified")),
+        _ => to_gender(request.gender),
+    }?;
+    let case = to_case(request.case)?;
+    Ok(petrovich.inflect(&part, &request.name, &gender, &case))
+}
+
+fn to_result(result: Result<String, Status>) -> proto::InflectResult {
+    let result = match result {
+        Ok(value) => inflect_result::Result::Value(value),
+        Err(status) => inflect_result::Result::Error(status.message().to_string()),
+    };
+    proto::InflectResult {
+        result: Some(result),
+    }
+}
+
+fn to_part(part: i32) -> Result<NamePart, Status> {
+    match proto::NamePart::try_from(part) {
+        Ok(proto::NamePart::LastName) => Ok(NamePart::LastName),
+        Ok(proto::NamePart::FirstName) => Ok(NamePart::FirstName),
+        Ok(proto::NamePart::MiddleName) => Ok(NamePart::MiddleName),
+        Ok(proto::NamePart::Unspecified) => {
+            Err(Status::invalid_argument("Name part is not specified"))
+        }
+        Err(_) => Err(unknown("name part", part)),
+    }
+}
+
+//Unspecified gender is detected by the middle name
+fn to_name_gender(gender: i32, name: &FullName) -> Result<Gender, Status> {
+    match proto::Gender::try_from(gender) {
+        Ok(proto::Gender::Unspecified) => Ok(name.gender()),
+        _ => to_gender(gender),
+    }
+}
+
+fn to_gender(gender: i32) -> Result<Gender, Status> {
+    match proto::Gender::try_from(gender) {
+        Ok(proto::Gender::Male) => Ok(Gender::Male),
+        Ok(proto::Gender::Female) => Ok(Gender::Female),
+        Ok(proto::Gender::Androgynous) => Ok(Gender::Androgynous),
+        _ => Err(unknown("gender", gender)),
+    }
+}
+
+fn to_proto_gender(gender: Gender) -> proto::Gender {
+    match gender {
+        Gender::Male => proto::Gender::Male,
+        Gender::Female => proto::Gender::Female,
+        Gender::Androgynous => proto::Gender::Androgynous,
+    }
+}
+
+fn to_case(case: i32) -> Result<Case, Status> {
+    match proto::Case::try_from(case) {
+        Ok(proto::Case::Nominative) => Ok(Case::Nominative),
+        Ok(proto::Case::Genitive) => Ok(Case::Genitive),
+        Ok(proto::Case::Dative) => Ok(Case::Dative),
+        Ok(proto::Case::Accusative) => Ok(Case::Accusative),
+        Ok(proto::Case::Instrumental) => Ok(Case::Instrumental),
+        Ok(proto::Case::Prepositional) => Ok(Case::Prepositional),
+        Ok(proto::Case::Unspecified) => Err(Status::invalid_argument("Case is not specified")),
+        Err(_) => Err(unknown("case", case)),
+    }
+}
+
+fn unknown(name: &str, value: i32) -> Status {
+    Status::invalid_argument(format!("Unknown {} {}", name, value))
+}
+
+fn to_full_name(name: Option<proto::FullName>) -> FullName {
+    let name = name.unwrap_or_default();
+    FullName::new(
+        name.last_name.as_deref().unwrap_or_default(),
+        name.first_name.as_deref().unwrap_or_default(),
+        name.middle_name.as_deref().unwrap_or_default(),
+    )
+}
+
+fn to_proto_name(name: FullName) -> proto::FullName {
+    proto::FullName {
+        last_name: name.last_name,
+        first_name: name.first_name,
+        middle_name: name.middle_name,
+    }
+}
@@ -0,0 +1,54 @@
+use clap::Parser;
+use petrovich_core::Petrovich;
+use petrovich_grpc::{PetrovichService, DEFAULT_MAX_BATCH_ITEMS};
+use std::error::Error;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tonic::transport::Server;
+
+#[derive(Parser)]
+#[command(about = "gRPC server inflecting Russian names")]
+struct Args {
+    #[arg(
+        long,
+        default_value = "127.0.0.1:50051",
+        help = "Address to listen on, only local connections by default"
+    )]
+    addr: SocketAddr,
+    #[arg(
+        long,
+        help = "Rules file, the embedded rules are used if the server is built with them"
+    )]
+    rules: Option<String>,
+    #[arg(
+        long,
+        default_value_t = DEFAULT_MAX_BATCH_ITEMS,
+        help = "Maximal number of names in a batch request"
+    )]
+    max_batch_items: usize,
+}
+
+fn load_petrovich(rules: Option<&str>) -> Result<Petrovich, Box<dyn Error>> {
+    match rules {
+        Some(rules) => Petrovich::new(rules),
+        #[cfg(feature = "embedded-rules")]
+        None => Petrovich::embedded(),
+        #[cfg(not(feature = "embedded-rules"))]
+        None => Err("No rules file given, use --rules".into()),
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    let petrovich = load_petrovich(args.rules.as_deref())?;
+    let service = PetrovichService::new(Arc::new(petrovich), args.max_batch_items);
+    println!("Listening on {}", args.addr);
+    Server::builder()
+        .add_service(service.into_server())
+        .serve_with_shutdown(args.addr, async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
+    Ok(())
+}
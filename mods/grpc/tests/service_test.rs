@@ -0,0 +1,186 @@
+use petrovich_core::Petrovich;
+use petrovich_grpc::proto::inflect_result::Result as InflectResult;
+use petrovich_grpc::proto::petrovich_client::PetrovichClient;
+use petrovich_grpc::proto::{self, Case, Gender, NamePart};
+use petrovich_grpc::PetrovichService;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tokio_stream::wrappers::TcpListenerStream;
+use tokio_stream::StreamExt;
+use tonic::transport::{Channel, Server};
+use tonic::Code;
+
+const RULES: &str = "
+firstname:
+  suffixes:
+    - gender: male
+      test: [н]
+      mods: [а, у, а, ом, е]
+lastname:
+  suffixes:
+    - gender: male
+      test: [ов]
+      mods: [а, у, а, ым, е]
+middlename:
+  suffixes:
+    - gender: male
+      test: [ич]
+      mods: [а, у, а, ем, е]
+";
+
+async fn start_server(max_batch_items: usize) -> PetrovichClient<Channel> {
+    let petrovich: Petrovich = RULES.parse().unwrap();
+    let service = PetrovichService::new(Arc::new(petrovich), max_batch_items);
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        Server::builder()
+            .add_service(service.into_server())
+            .serve_with_incoming(TcpListenerStream::new(listener))
+            .await
+            .unwrap()
+    });
+    PetrovichClient::connect(url).await.unwrap()
+}
+
+fn inflect_request(part: NamePart, name: &str, case: Case) -> proto::InflectRequest {
+    proto::InflectRequest {
+        part: part as i32,
+        name: name.to_string(),
+        gender: Gender::Male as i32,
+        case: case as i32,
+    }
+}
+
+fn full_name(last_name: &str, first_name: &str, middle_name: &str) -> proto::FullName {
+    proto::FullName {
+        last_name: Some(last_name.to_string()),
+        first_name: Some(first_name.to_string()),
+        middle_name: Some(middle_name.to_string()),
+    }
+}
+
+#[tokio::test]
+async fn inflect_test() {
+    let mut client = start_server(10).await;
+    let request = inflect_request(NamePart::LastName, "Иванов", Case::Dative);
+    let response = client.inflect(request).await.unwrap();
+    assert_eq!("иванову", response.into_inner().result);
+
+    let mut request = inflect_request(NamePart::LastName, "Иванов", Case::Dative);
+    request.gender = Gender::Unspecified as i32;
+    let status = client.inflect(request).await.unwrap_err();
+    assert_eq!(Code::InvalidArgument, status.code());
+    assert_eq!("Gender is not specified", status.message());
+
+    let mut request = inflect_request(NamePart::LastName, "Иванов", Case::Dative);
+    request.case = 42;
+    let status = client.inflect(request).await.unwrap_err();
+    assert_eq!("Unknown case 42", status.message());
+}
+
+#[tokio::test]
+async fn inflect_full_name_test() {
+    let mut client = start_server(10).await;
+    let request = proto::InflectFullNameRequest {
+        name: Some(full_name("Иванов", "Иван", "Иванович")),
+        gender: Gender::Unspecified as i32,
+        case: Case::Instrumental as i32,
+    };
+    let response = client.inflect_full_name(request).await.unwrap();
+    assert_eq!(
+        full_name("Ивановым", "Иваном", "Ивановичем"),
+        response.into_inner()
+    );
+}
+
+#[tokio::test]
+async fn declension_test() {
+    let mut client = start_server(10).await;
+    let request = proto::DeclensionRequest {
+        name: Some(full_name("Иванов", "Иван", "Иванович")),
+        gender: Gender::Male as i32,
+    };
+    let forms = client.declension(request).await.unwrap().into_inner().forms;
+    assert_eq!(6, forms.len());
+    assert_eq!(Case::Nominative as i32, forms[0].case);
+    assert_eq!(Some(full_name("Иванов", "Иван", "Иванович")), forms[0].name);
+    assert_eq!(Case::Genitive as i32, forms[1].case);
+    assert_eq!(
+        Some(full_name("Иванова", "Ивана", "Ивановича")),
+        forms[1].name
+    );
+}
+
+#[tokio::test]
+async fn detect_gender_test() {
+    let mut client = start_server(10).await;
+    for (middle_name, expected) in [
+        ("Иванович", Gender::Male),
+        ("Ивановна", Gender::Female),
+        (" ИВАНОВИЧ", Gender::Male),
+        ("", Gender::Androgynous),
+    ] {
+        let request = proto::DetectGenderRequest {
+            middle_name: middle_name.to_string(),
+        };
+        let response = client.detect_gender(request).await.unwrap();
+        assert_eq!(expected as i32, response.into_inner().gender);
+    }
+}
+
+#[tokio::test]
+async fn batch_inflect_test() {
+    let mut client = start_server(2).await;
+    let request = proto::BatchInflectRequest {
+        requests: vec![
+            inflect_request(NamePart::FirstName, "Иван", Case::Genitive),
+            inflect_request(NamePart::Unspecified, "Иван", Case::Genitive),
+        ],
+    };
+    let results = client
+        .batch_inflect(request)
+        .await
+        .unwrap()
+        .into_inner()
+        .results;
+    let results: Vec<_> = results.into_iter().map(|result| result.result).collect();
+    assert_eq!(
+        vec![
+            Some(InflectResult::Value("ивана".to_string())),
+            Some(InflectResult::Error(
+                "Name part is not specified".to_string()
+            )),
+        ],
+        results
+    );
+
+    let request = proto::BatchInflectRequest {
+        requests: vec![inflect_request(NamePart::FirstName, "Иван", Case::Genitive); 3],
+    };
+    let status = client.batch_inflect(request).await.unwrap_err();
+    assert_eq!(Code::ResourceExhausted, status.code());
+}
+
+#[tokio::test]
+async fn stream_inflect_test() {
+    let mut client = start_server(10).await;
+    let requests = tokio_stream::iter(vec![
+        inflect_request(NamePart::FirstName, "Иван", Case::Dative),
+        inflect_request(NamePart::LastName, "Иванов", Case::Prepositional),
+        inflect_request(NamePart::MiddleName, "Иванович", Case::Unspecified),
+    ]);
+    let mut results = client.stream_inflect(requests).await.unwrap().into_inner();
+    let mut actual = Vec::new();
+    while let Some(result) = results.next().await {
+        actual.push(result.unwrap().result.unwrap());
+    }
+    assert_eq!(
+        vec![
+            InflectResult::Value("ивану".to_string()),
+            InflectResult::Value("иванове".to_string()),
+            InflectResult::Error("Case is not specified".to_string()),
+        ],
+        actual
+    );
+}
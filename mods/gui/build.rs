@@ -1,4 +1,8 @@
 use gtk::gio;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
 
 fn main() {
     gio::compile_resources(
@@ -6,4 +10,34 @@ fn main() {
         "src/resources/resources.gresource.xml",
         "compiled.gresource",
     );
+    compile_catalogs();
+}
+
+// Catalogs of po/LINGUAS are compiled with msgfmt into the build directory,
+// packages set PETROVICH_LOCALEDIR to the installed catalogs instead
+fn compile_catalogs() {
+    println!("cargo:rerun-if-changed=po");
+    println!("cargo:rerun-if-env-changed=PETROVICH_LOCALEDIR");
+    if let Ok(dir) = env::var("PETROVICH_LOCALEDIR") {
+        println!("cargo:rustc-env=PETROVICH_LOCALEDIR={}", dir);
+        return;
+    }
+    let locale = PathBuf::from(env::var("OUT_DIR").unwrap()).join("locale");
+    let languages = fs::read_to_string("po/LINGUAS").expect("Failed to read po/LINGUAS");
+    for language in languages.lines().filter(|line| !line.starts_with('#')) {
+        let dir = locale.join(language).join("LC_MESSAGES");
+        fs::create_dir_all(&dir).expect("Failed to create the locale directory");
+        let status = Command::new("msgfmt")
+            .arg("-o")
+            .arg(dir.join("petrovich.mo"))
+            .arg(format!("po/{}.po", language))
+            .status();
+        if !matches!(status, Ok(status) if status.success()) {
+            println!(
+                "cargo:warning=Failed to compile po/{}.po with msgfmt",
+                language
+            );
+        }
+    }
+    println!("cargo:rustc-env=PETROVICH_LOCALEDIR={}", locale.display());
 }
@@ -0,0 +1,311 @@
+use std::error::Error;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use petrovich_core::case::Case;
+use petrovich_core::name::{FullName, NamePart};
+use petrovich_core::Petrovich;
+
+use crate::query::GenderChoice;
+
+//Rows shown on the batch page before export
+pub const PREVIEW_ROWS: usize = 5;
+
+//First item of the column dropdowns
+pub const NO_COLUMN: &str = "—";
+
+//Lowercase header prefixes used to preselect the columns
+pub const LAST_NAME_HEADERS: &[&str] = &["фамилия", "last", "surname"];
+pub const FIRST_NAME_HEADERS: &[&str] = &["имя", "first"];
+pub const MIDDLE_NAME_HEADERS: &[&str] = &["отчество", "middle", "patronymic", "patronimic"];
+
+//Contents of a CSV or TSV file, the first line is a header
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct Table {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+//Tab for .tsv and .tab files, comma otherwise
+pub fn delimiter_of(path: &Path) -> u8 {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("tsv") || ext.eq_ignore_ascii_case("tab") => b'\t',
+        _ => b',',
+    }
+}
+
+impl Table {
+    pub fn load_from_file(path: &Path) -> Result<Table, Box<dyn Error>> {
+        Table::read(File::open(path)?, delimiter_of(path))
+    }
+
+    pub fn read(reader: impl Read, delimiter: u8) -> Result<Table, Box<dyn Error>> {
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(delimiter)
+            .flexible(true)
+            .from_reader(reader);
+        let headers = reader.headers()?.iter().map(str::to_string).collect();
+        let mut rows = Vec::new();
+        for record in reader.records() {
+            rows.push(record?.iter().map(str::to_string).collect());
+        }
+        Ok(Table { headers, rows })
+    }
+
+    pub fn save_to_file(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        self.write(File::create(path)?, delimiter_of(path))
+    }
+
+    pub fn write(&self, writer: impl Write, delimiter: u8) -> Result<(), Box<dyn Error>> {
+        let mut writer = csv::WriterBuilder::new()
+            .delimiter(delimiter)
+            .flexible(true)
+            .from_writer(writer);
+        writer.write_record(&self.headers)?;
+        for row in &self.rows {
+            writer.write_record(row)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    pub fn head(&self, rows: usize) -> Table {
+        Table {
+            headers: self.headers.clone(),
+            rows: self.rows.iter().take(rows).cloned().collect(),
+        }
+    }
+}
+
+//Column of a dropdown item, the items are `NO_COLUMN` followed by the headers
+pub fn column_of(selected: u32, table: &Table) -> Option<usize> {
+    (selected as usize)
+        .checked_sub(1)
+        .filter(|column| *column < table.headers.len())
+}
+
+//Dropdown item of the first header starting with one of `prefixes`
+pub fn guess_column(table: &Table, prefixes: &[&str]) -> u32 {
+    table
+        .headers
+        .iter()
+        .position(|header| {
+            let header = header.trim().to_lowercase();
+            prefixes.iter().any(|prefix| header.starts_with(prefix))
+        })
+        .map_or(0, |column| column as u32 + 1)
+}
+
+//Settings of the batch page: which columns hold name parts and what cases to add
+pub struct BatchJob {
+    pub last_name: Option<usize>,
+    pub first_name: Option<usize>,
+    pub middle_name: Option<usize>,
+    pub gender: GenderChoice,
+    pub cases: Vec<Case>,
+}
+
+impl BatchJob {
+    fn columns(&self) -> Vec<(NamePart, usize)> {
+        [
+            (NamePart::LastName, self.last_name),
+            (NamePart::FirstName, self.first_name),
+            (NamePart::MiddleName, self.middle_name),
+        ]
+        .into_iter()
+        .filter_map(|(part, column)| column.map(|column| (part, column)))
+        .collect()
+    }
+
+    //Source columns followed by "<column> (<case>)" for every case and name column
+    pub fn headers(&self, table: &Table) -> Vec<String> {
+        let mut headers = table.headers.clone();
+        for case in &self.cases {
+            for (_, column) in self.columns() {
+                let header = table
+                    .headers
+                    .get(column)
+                    .map(String::as_str)
+                    .unwrap_or_default();
+                headers.push(format!("{} ({})", header, case));
+            }
+        }
+        headers
+    }
+
+    pub fn inflect_row(&self, petrovich: &Petrovich, row: &[String]) -> Vec<String> {
+        let cell = |column: Option<usize>| {
+            column
+                .and_then(|column| row.get(column))
+                .map(String::as_str)
+                .unwrap_or_default()
+        };
+        let name = FullName::new(
+            cell(self.last_name),
+            cell(self.first_name),
+            cell(self.middle_name),
+        );
+        let gender = self.gender.gender(cell(self.middle_name));
+        let mut result = row.to_vec();
+        for case in &self.cases {
+            let inflected = petrovich.full_name(&name, &gender, case);
+            for (part, _) in self.columns() {
+                result.push(inflected.get(&part).unwrap_or_default().to_string());
+            }
+        }
+        result
+    }
+
+    //None if cancelled, `progress` gets the number of finished rows
+    pub fn run(
+        &self,
+        petrovich: &Petrovich,
+        table: &Table,
+        cancel: &AtomicBool,
+        mut progress: impl FnMut(usize),
+    ) -> Option<Table> {
+        let mut rows = Vec::with_capacity(table.rows.len());
+        for row in &table.rows {
+            if cancel.load(Ordering::Relaxed) {
+                return None;
+            }
+            rows.push(self.inflect_row(petrovich, row));
+            progress(rows.len());
+        }
+        Some(Table {
+            headers: self.headers(table),
+            rows,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_petrovich() -> Petrovich {
+        "
+lastname:
+  suffixes:
+    - gender: male
+      test: [ов]
+      mods: [а, у, а, ым, е]
+    - gender: female
+      test: [ова]
+      mods: [-ой, -ой, -у, -ой, -ой]
+firstname:
+  suffixes:
+    - gender: male
+      test: [н]
+      mods: [а, у, а, ом, е]
+    - gender: female
+      test: [а]
+      mods: [-ы, -е, -у, -ой, -е]
+middlename:
+  suffixes:
+    - gender: male
+      test: [ич]
+      mods: [а, у, а, ем, е]
+    - gender: female
+      test: [на]
+      mods: [-ы, -е, -у, -ой, -е]
+"
+        .parse()
+        .unwrap()
+    }
+
+    fn job() -> BatchJob {
+        BatchJob {
+            last_name: Some(1),
+            first_name: Some(2),
+            middle_name: Some(3),
+            gender: GenderChoice::Auto,
+            cases: vec![Case::Genitive, Case::Dative],
+        }
+    }
+
+    #[test]
+    fn read_write_test() {
+        let input = "id\tФамилия\tИмя\n1\tИванов\t\"Иван, мл.\"\n2\tПетрова\n";
+        let table = Table::read(input.as_bytes(), b'\t').unwrap();
+        assert_eq!(vec!["id", "Фамилия", "Имя"], table.headers);
+        assert_eq!(vec!["2", "Петрова"], table.rows[1]);
+        assert_eq!(1, table.head(1).rows.len());
+
+        let mut output = Vec::new();
+        table.write(&mut output, b',').unwrap();
+        let expected = "id,Фамилия,Имя\n1,Иванов,\"Иван, мл.\"\n2,Петрова\n";
+        assert_eq!(expected, String::from_utf8(output).unwrap());
+
+        assert_eq!(b'\t', delimiter_of(Path::new("staff.TSV")));
+        assert_eq!(b',', delimiter_of(Path::new("staff.csv")));
+    }
+
+    #[test]
+    fn run_test() {
+        let input = "id,last,first,middle\n1,Иванов,Иван,Петрович\n2,Петрова,Анна,Сергеевна\n";
+        let table = Table::read(input.as_bytes(), b',').unwrap();
+        let mut finished = Vec::new();
+        let actual = job()
+            .run(&test_petrovich(), &table, &AtomicBool::new(false), |done| {
+                finished.push(done)
+            })
+            .unwrap();
+        assert_eq!(vec![1, 2], finished);
+        assert_eq!(
+            vec![
+                "id",
+                "last",
+                "first",
+                "middle",
+                "last (genitive)",
+                "first (genitive)",
+                "middle (genitive)",
+                "last (dative)",
+                "first (dative)",
+                "middle (dative)",
+            ],
+            actual.headers
+        );
+        assert_eq!(
+            vec![
+                "2",
+                "Петрова",
+                "Анна",
+                "Сергеевна",
+                "Петровой",
+                "Анны",
+                "Сергеевны",
+                "Петровой",
+                "Анне",
+                "Сергеевне",
+            ],
+            actual.rows[1]
+        );
+    }
+
+    #[test]
+    fn columns_test() {
+        let input = "ID,Фамилия,Имя,Отчество\n";
+        let table = Table::read(input.as_bytes(), b',').unwrap();
+        assert_eq!(2, guess_column(&table, LAST_NAME_HEADERS));
+        assert_eq!(4, guess_column(&table, MIDDLE_NAME_HEADERS));
+        assert_eq!(0, guess_column(&table, &["gender"]));
+        let input = "Surname,Name suffix,First name\n";
+        let table = Table::read(input.as_bytes(), b',').unwrap();
+        assert_eq!(3, guess_column(&table, FIRST_NAME_HEADERS));
+        assert_eq!(None, column_of(0, &table));
+        assert_eq!(Some(1), column_of(2, &table));
+        assert_eq!(None, column_of(u32::MAX, &table));
+    }
+
+    #[test]
+    fn cancel_test() {
+        let input = "last\nИванов\n";
+        let table = Table::read(input.as_bytes(), b',').unwrap();
+        let actual = job().run(&test_petrovich(), &table, &AtomicBool::new(true), |_| {});
+        assert_eq!(None, actual);
+    }
+}
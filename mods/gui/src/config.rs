@@ -0,0 +1,94 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const APP_DIR: &str = "petrovich";
+const RULES_FILE: &str = "rules-file";
+const OVERRIDES_FILE: &str = "overrides.yml";
+const HISTORY_FILE: &str = "history.tsv";
+
+//Settings kept between runs in the user config directory, e.g. ~/.config/petrovich
+pub struct Config {
+    dir: PathBuf,
+}
+
+impl Config {
+    pub fn new(config_dir: &Path) -> Config {
+        Config {
+            dir: config_dir.join(APP_DIR),
+        }
+    }
+
+    //The last rules file opened by the user
+    pub fn rules_file(&self) -> Option<PathBuf> {
+        let value = fs::read_to_string(self.dir.join(RULES_FILE)).ok()?;
+        let value = value.trim();
+        if value.is_empty() {
+            None
+        } else {
+            Some(PathBuf::from(value))
+        }
+    }
+
+    pub fn set_rules_file(&self, path: &Path) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.dir.join(RULES_FILE), path.to_string_lossy().as_bytes())
+    }
+
+    //User override rules in the rules file format, None if there are no overrides yet
+    pub fn overrides(&self) -> io::Result<Option<String>> {
+        match fs::read_to_string(self.dir.join(OVERRIDES_FILE)) {
+            Ok(content) => Ok(Some(content)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn set_overrides(&self, content: &str) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.dir.join(OVERRIDES_FILE), content)
+    }
+
+    //Recent queries, empty before the first one
+    pub fn history(&self) -> String {
+        fs::read_to_string(self.dir.join(HISTORY_FILE)).unwrap_or_default()
+    }
+
+    pub fn set_history(&self, content: &str) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.dir.join(HISTORY_FILE), content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    #[test]
+    fn rules_file_test() {
+        let dir = env::temp_dir().join(format!("petrovich-config-test-{}", process::id()));
+        let config = Config::new(&dir);
+        assert_eq!(None, config.rules_file());
+
+        config.set_rules_file(Path::new("/tmp/rules.yml")).unwrap();
+        assert_eq!(Some(PathBuf::from("/tmp/rules.yml")), config.rules_file());
+        assert_eq!(
+            Some(PathBuf::from("/tmp/rules.yml")),
+            Config::new(&dir).rules_file()
+        );
+
+        assert_eq!(None, config.overrides().unwrap());
+        config.set_overrides("lastname: {}\n").unwrap();
+        assert_eq!(
+            Some("lastname: {}\n".to_string()),
+            config.overrides().unwrap()
+        );
+
+        assert_eq!("", config.history());
+        config.set_history("Иванов\t\t\t0\tgenitive\n").unwrap();
+        assert_eq!("Иванов\t\t\t0\tgenitive\n", config.history());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
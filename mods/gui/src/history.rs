@@ -0,0 +1,170 @@
+use petrovich_core::case::Case;
+
+use crate::query::{self, GenderChoice, Query};
+
+//Number of kept queries
+pub const HISTORY_SIZE: usize = 50;
+
+//Recent queries of the name page, the most recent first
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct History {
+    queries: Vec<Query>,
+}
+
+impl History {
+    //One query per line: last name, first name, middle name, gender item and case separated by tabs.
+    //Broken lines are skipped, the history is not worth an error.
+    pub fn load_from_str(content: &str) -> History {
+        let queries = content
+            .lines()
+            .filter_map(query_of)
+            .take(HISTORY_SIZE)
+            .collect();
+        History { queries }
+    }
+
+    pub fn to_text(&self) -> String {
+        self.queries
+            .iter()
+            .map(|query| {
+                format!(
+                    "{}\t{}\t{}\t{}\t{}\n",
+                    query.last_name,
+                    query.first_name,
+                    query.middle_name,
+                    query.gender.index(),
+                    query.case
+                )
+            })
+            .collect()
+    }
+
+    pub fn queries(&self) -> &[Query] {
+        &self.queries
+    }
+
+    //A repeated query is moved to the top, queries without a name are not kept
+    pub fn add(&mut self, query: &Query) {
+        let query = Query {
+            last_name: clean(&query.last_name),
+            first_name: clean(&query.first_name),
+            middle_name: clean(&query.middle_name),
+            ..query.clone()
+        };
+        if query.name().to_string().is_empty() {
+            return;
+        }
+        self.queries.retain(|other| *other != query);
+        self.queries.insert(0, query);
+        self.queries.truncate(HISTORY_SIZE);
+    }
+
+    pub fn search(&self, text: &str) -> Vec<&Query> {
+        self.queries
+            .iter()
+            .filter(|query| matches(query, text))
+            .collect()
+    }
+}
+
+//Any name part contains the text, ignoring case
+pub fn matches(query: &Query, text: &str) -> bool {
+    let text = text.trim().to_lowercase();
+    [&query.last_name, &query.first_name, &query.middle_name]
+        .iter()
+        .any(|part| part.to_lowercase().contains(&text))
+}
+
+//A row of the history list: "Иванов Иван Иванович — Genitive, Auto"
+pub fn title(query: &Query) -> String {
+    format!(
+        "{} — {}, {}",
+        query.name(),
+        query::case_title(&query.case),
+        query.gender.title()
+    )
+}
+
+fn clean(value: &str) -> String {
+    value.trim().replace('\t', " ")
+}
+
+fn query_of(line: &str) -> Option<Query> {
+    let fields: Vec<&str> = line.split('\t').collect();
+    match fields[..] {
+        [last_name, first_name, middle_name, gender, case] => Some(Query {
+            last_name: last_name.to_string(),
+            first_name: first_name.to_string(),
+            middle_name: middle_name.to_string(),
+            gender: GenderChoice::of(gender.parse().ok()?)?,
+            case: Case::of(case)?,
+        }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(last_name: &str, first_name: &str, case: Case) -> Query {
+        Query {
+            last_name: last_name.to_string(),
+            first_name: first_name.to_string(),
+            middle_name: String::new(),
+            gender: GenderChoice::Auto,
+            case,
+        }
+    }
+
+    #[test]
+    fn add_test() {
+        let mut history = History::default();
+        history.add(&query(" Иванов ", "Иван", Case::Genitive));
+        history.add(&query("Петров", "", Case::Dative));
+        history.add(&query("", " ", Case::Dative));
+        history.add(&query("Иванов", "Иван", Case::Genitive));
+        let expected = vec![
+            query("Иванов", "Иван", Case::Genitive),
+            query("Петров", "", Case::Dative),
+        ];
+        assert_eq!(expected, history.queries());
+
+        for index in 0..HISTORY_SIZE {
+            history.add(&query(&format!("Сидоров{}", index), "", Case::Dative));
+        }
+        assert_eq!(HISTORY_SIZE, history.queries().len());
+        assert_eq!("Сидоров49", history.queries()[0].last_name);
+    }
+
+    #[test]
+    fn text_test() {
+        let mut history = History::default();
+        history.add(&query("Петров", "", Case::Dative));
+        history.add(&query("Иванов", "Иван", Case::Genitive));
+        let text = history.to_text();
+        assert_eq!("Иванов\tИван\t\t0\tgenitive\nПетров\t\t\t0\tdative\n", text);
+        assert_eq!(history, History::load_from_str(&text));
+
+        let actual =
+            History::load_from_str("Иванов\t\t\t9\tgenitive\nИванов\nПетров\t\t\t1\tdative\n");
+        let mut expected = query("Петров", "", Case::Dative);
+        expected.gender = GenderChoice::Male;
+        assert_eq!(vec![expected], actual.queries());
+    }
+
+    #[test]
+    fn search_test() {
+        let mut history = History::default();
+        history.add(&query("Петров", "Иван", Case::Dative));
+        history.add(&query("Иванова", "Анна", Case::Genitive));
+        assert_eq!(2, history.search("иван").len());
+        assert_eq!(vec![&history.queries()[0]], history.search(" АННА"));
+        assert_eq!(2, history.search("").len());
+        assert!(history.search("Сидоров").is_empty());
+        assert_eq!(
+            "Иванова Анна — Genitive, Auto",
+            title(&history.queries()[0])
+        );
+    }
+}
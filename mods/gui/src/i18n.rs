@@ -0,0 +1,207 @@
+use gettextrs::{bind_textdomain_codeset, bindtextdomain, setlocale, textdomain, LocaleCategory};
+use std::fmt::Display;
+
+pub const DOMAIN: &str = "petrovich";
+
+// Directory of the compiled catalogs, set by build.rs
+const LOCALEDIR: &str = env!("PETROVICH_LOCALEDIR");
+
+// gettext with "{}" placeholders filled in order: tr!("Failed to read {}: {}", path, e)
+macro_rules! tr {
+    ($msgid:literal $(, $arg:expr)* $(,)?) => {
+        $crate::i18n::fill(&gettextrs::gettext($msgid), &[$(&$arg as &dyn std::fmt::Display),*])
+    };
+}
+pub(crate) use tr;
+
+// Called before the window is built, the strings stay in English without catalogs
+pub fn init() {
+    setlocale(LocaleCategory::LcAll, "");
+    let result = bindtextdomain(DOMAIN, LOCALEDIR)
+        .and_then(|_| bind_textdomain_codeset(DOMAIN, "UTF-8"))
+        .and_then(|_| textdomain(DOMAIN));
+    if let Err(e) = result {
+        eprintln!("Failed to set up translations: {}", e);
+    }
+}
+
+pub fn fill(template: &str, args: &[&dyn Display]) -> String {
+    let mut args = args.iter();
+    let mut result = String::new();
+    for (index, part) in template.split("{}").enumerate() {
+        if index > 0 {
+            match args.next() {
+                Some(arg) => result.push_str(&arg.to_string()),
+                None => result.push_str("{}"),
+            }
+        }
+        result.push_str(part);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    const CATALOGS: [(&str, &str); 2] = [
+        ("en", include_str!("../po/en.po")),
+        ("ru", include_str!("../po/ru.po")),
+    ];
+
+    const SOURCES: [&str; 6] = [
+        include_str!("main.rs"),
+        include_str!("query.rs"),
+        include_str!("custom_button/mod.rs"),
+        include_str!("table_row/mod.rs"),
+        include_str!("window/imp.rs"),
+        include_str!("window/mod.rs"),
+    ];
+
+    // Setters of text shown to users, their arguments must be translated
+    const SETTERS: [&str; 12] = [
+        "set_name(",
+        "set_title(",
+        "set_label(",
+        "set_text(",
+        "set_tooltip_text(",
+        "set_placeholder_text(",
+        "set_accept_label(",
+        "set_cancel_label(",
+        "set_message(",
+        "Label::new(",
+        "with_label(",
+        "with_mnemonic(",
+    ];
+
+    const UI: [&str; 2] = [
+        include_str!("resources/window.ui"),
+        include_str!("resources/gtk/help-overlay.ui"),
+    ];
+
+    // msgid -> msgstr, strings split into several lines are joined
+    fn catalog(content: &str) -> HashMap<String, String> {
+        let mut result = HashMap::new();
+        let (mut msgid, mut msgstr) = (String::new(), String::new());
+        let mut in_msgstr = false;
+        for line in content.lines().map(str::trim) {
+            if let Some(value) = line.strip_prefix("msgid ") {
+                if !msgid.is_empty() {
+                    result.insert(msgid, msgstr);
+                }
+                msgid = unquote(value);
+                msgstr = String::new();
+                in_msgstr = false;
+            } else if let Some(value) = line.strip_prefix("msgstr ") {
+                msgstr = unquote(value);
+                in_msgstr = true;
+            } else if line.starts_with('"') {
+                if in_msgstr {
+                    msgstr.push_str(&unquote(line));
+                } else {
+                    msgid.push_str(&unquote(line));
+                }
+            }
+        }
+        if !msgid.is_empty() {
+            result.insert(msgid, msgstr);
+        }
+        result
+    }
+
+    fn unquote(value: &str) -> String {
+        let value = value.trim().trim_start_matches('"').trim_end_matches('"');
+        value.replace("\\\"", "\"").replace("\\\\", "\\")
+    }
+
+    // Text of the elements marked as translatable
+    fn ui_strings() -> Vec<String> {
+        UI.iter()
+            .flat_map(|ui| ui.split("translatable=\"yes\">").skip(1))
+            .map(|part| part.split('<').next().unwrap_or_default())
+            .map(|text| {
+                text.replace("&lt;", "<")
+                    .replace("&gt;", ">")
+                    .replace("&amp;", "&")
+            })
+            .collect()
+    }
+
+    // First arguments of `gettext` and `tr!` calls
+    fn source_strings() -> Vec<String> {
+        let mut result = Vec::new();
+        for source in SOURCES {
+            for call in ["gettext(", "tr!("] {
+                for part in source.split(call).skip(1) {
+                    let part = part.trim_start();
+                    if let Some(rest) = part.strip_prefix('"') {
+                        let end = rest
+                            .char_indices()
+                            .find(|&(index, c)| c == '"' && !rest[..index].ends_with('\\'))
+                            .map(|(index, _)| index)
+                            .unwrap_or_default();
+                        result.push(unquote(&rest[..end]));
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    // String literals passed to the setters as is: `set_name(Some("YAML rules"))`
+    fn untranslated_strings() -> Vec<String> {
+        let mut result = Vec::new();
+        for source in SOURCES {
+            for setter in SETTERS {
+                for part in source.split(setter).skip(1) {
+                    let part = part.trim_start();
+                    let part = part.strip_prefix("Some(").unwrap_or(part).trim_start();
+                    let part = part.strip_prefix('&').unwrap_or(part);
+                    if part.starts_with('"') {
+                        let line = part.lines().next().unwrap_or_default();
+                        result.push(format!("{}{}", setter, line));
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn fill_test() {
+        assert_eq!("a 1 b x", fill("a {} b {}", &[&1, &"x"]));
+        assert_eq!("a 1 b {}", fill("a {} b {}", &[&1]));
+        assert_eq!("no args", fill("no args", &[]));
+    }
+
+    #[test]
+    fn translations_test() {
+        let ui = ui_strings();
+        let sources = source_strings();
+        assert!(ui.contains(&"Find!".to_string()));
+        assert!(sources.contains(&"Nominative".to_string()));
+        assert!(sources.contains(&"Failed to load rules from {}: {}".to_string()));
+        assert!(sources.contains(&"YAML rules".to_string()));
+        assert_eq!(Vec::<String>::new(), untranslated_strings());
+        for (language, content) in CATALOGS {
+            let catalog = catalog(content);
+            for msgid in ui.iter().chain(&sources) {
+                let msgstr = catalog.get(msgid).map(String::as_str).unwrap_or_default();
+                assert!(
+                    !msgstr.is_empty(),
+                    "No {} translation of '{}'",
+                    language,
+                    msgid
+                );
+                assert_eq!(
+                    msgid.matches("{}").count(),
+                    msgstr.matches("{}").count(),
+                    "Placeholders of '{}' in the {} translation",
+                    msgid,
+                    language
+                );
+            }
+        }
+    }
+}
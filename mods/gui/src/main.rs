@@ -1,4 +1,10 @@
+mod batch;
+mod config;
 mod custom_button;
+mod history;
+mod i18n;
+mod query;
+mod table_row;
 mod window;
 
 use gio::{prelude::*, SimpleAction};
@@ -9,22 +15,47 @@ use window::Window;
 const APP_ID: &str = "com.man.petrovich";
 
 fn main() {
+    i18n::init();
     gio::resources_register_include!("compiled.gresource").expect("Failed to register resources.");
 
     let app = Application::builder().application_id(APP_ID).build();
     app.connect_activate(build_ui);
     app.set_accels_for_action("win.close", &["<Ctrl>Q"]);
+    app.set_accels_for_action("win.copy-all", &["<Ctrl><Shift>C"]);
+    app.set_accels_for_action("win.open-rules", &["<Ctrl>O"]);
+    app.set_accels_for_action("win.copy-result", &["<Ctrl>C"]);
+    app.set_accels_for_action("win.edit-override", &["<Ctrl>E"]);
+    app.set_accels_for_action("win.history", &["<Ctrl>H"]);
+    app.set_accels_for_action("win.next-case", &["<Ctrl>Page_Down"]);
+    app.set_accels_for_action("win.previous-case", &["<Ctrl>Page_Up"]);
+    app.set_accels_for_action("win.next-gender", &["<Ctrl>G"]);
+    app.set_accels_for_action("win.previous-gender", &["<Ctrl><Shift>G"]);
     app.run();
 }
 
 fn build_ui(app: &Application) {
     let window = Window::new(app);
 
-    let action_close = SimpleAction::new("close", None);
-    action_close.connect_activate(clone!(@weak window => move |_, _| {
-        window.close();
-    }));
-    window.add_action(&action_close);
+    add_action(&window, "close", |window| window.close());
+    add_action(&window, "copy-all", Window::copy_all);
+    add_action(&window, "copy-result", Window::copy_result);
+    add_action(&window, "open-rules", Window::open_rules);
+    add_action(&window, "open-batch", Window::open_batch);
+    add_action(&window, "overrides", Window::show_overrides);
+    add_action(&window, "edit-override", Window::edit_override);
+    add_action(&window, "history", Window::show_history);
+    add_action(&window, "next-case", |window| window.cycle_case(1));
+    add_action(&window, "previous-case", |window| window.cycle_case(-1));
+    add_action(&window, "next-gender", |window| window.cycle_gender(1));
+    add_action(&window, "previous-gender", |window| window.cycle_gender(-1));
 
     window.present();
 }
+
+fn add_action(window: &Window, name: &str, activate: impl Fn(&Window) + 'static) {
+    let action = SimpleAction::new(name, None);
+    action.connect_activate(clone!(@weak window => move |_, _| {
+        activate(&window);
+    }));
+    window.add_action(&action);
+}
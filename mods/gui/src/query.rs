@@ -0,0 +1,435 @@
+use crate::i18n::tr;
+use gettextrs::gettext;
+use petrovich_core::case::Case;
+use petrovich_core::gender::Gender;
+use petrovich_core::inflection::{MatchKind, WordInflection};
+use petrovich_core::name::{FullName, NamePart};
+use petrovich_core::overlay::Override;
+use petrovich_core::Petrovich;
+use std::error::Error;
+
+//Items of the gender dropdown
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum GenderChoice {
+    //detected by the middle name
+    Auto,
+    Male,
+    Female,
+    Androgynous,
+}
+
+impl GenderChoice {
+    pub fn values() -> [GenderChoice; 4] {
+        [
+            GenderChoice::Auto,
+            GenderChoice::Male,
+            GenderChoice::Female,
+            GenderChoice::Androgynous,
+        ]
+    }
+
+    pub fn of(index: u32) -> Option<GenderChoice> {
+        match index {
+            0 => Some(GenderChoice::Auto),
+            1 => Some(GenderChoice::Male),
+            2 => Some(GenderChoice::Female),
+            3 => Some(GenderChoice::Androgynous),
+            _ => None,
+        }
+    }
+
+    pub fn index(&self) -> u32 {
+        GenderChoice::values()
+            .iter()
+            .position(|choice| choice == self)
+            .unwrap_or_default() as u32
+    }
+
+    pub fn gender(&self, middle_name: &str) -> Gender {
+        match self {
+            GenderChoice::Auto => Gender::detect_gender(middle_name),
+            GenderChoice::Male => Gender::Male,
+            GenderChoice::Female => Gender::Female,
+            GenderChoice::Androgynous => Gender::Androgynous,
+        }
+    }
+
+    pub fn title(&self) -> String {
+        match self {
+            GenderChoice::Auto => gettext("Auto"),
+            GenderChoice::Male => gender_title(&Gender::Male),
+            GenderChoice::Female => gender_title(&Gender::Female),
+            GenderChoice::Androgynous => gender_title(&Gender::Androgynous),
+        }
+    }
+}
+
+//Localized names of the dropdown items
+pub fn gender_title(gender: &Gender) -> String {
+    match gender {
+        Gender::Male => gettext("Male"),
+        Gender::Female => gettext("Female"),
+        Gender::Androgynous => gettext("Androgynous"),
+    }
+}
+
+pub fn case_title(case: &Case) -> String {
+    match case {
+        Case::Nominative => gettext("Nominative"),
+        Case::Genitive => gettext("Genitive"),
+        Case::Dative => gettext("Dative"),
+        Case::Accusative => gettext("Accusative"),
+        Case::Instrumental => gettext("Instrumental"),
+        Case::Prepositional => gettext("Prepositional"),
+    }
+}
+
+//Cases in the order of the case dropdown and the declension table
+pub const CASES: [Case; 6] = [
+    Case::Nominative,
+    Case::Genitive,
+    Case::Dative,
+    Case::Accusative,
+    Case::Instrumental,
+    Case::Prepositional,
+];
+
+//Items of the case dropdown
+pub fn case_of(index: u32) -> Option<Case> {
+    match index {
+        0 => Some(Case::Nominative),
+        1 => Some(Case::Genitive),
+        2 => Some(Case::Dative),
+        3 => Some(Case::Accusative),
+        4 => Some(Case::Instrumental),
+        5 => Some(Case::Prepositional),
+        _ => None,
+    }
+}
+
+pub fn case_index(case: &Case) -> u32 {
+    CASES
+        .iter()
+        .position(|other| other == case)
+        .unwrap_or_default() as u32
+}
+
+//Dropdown item after moving by the step, wrapping around at both ends
+pub fn cycle(selected: u32, count: u32, step: i32) -> u32 {
+    if count == 0 {
+        return selected;
+    }
+    (selected as i64 + step as i64).rem_euclid(count as i64) as u32
+}
+
+//Values of the window entries and dropdowns
+#[derive(PartialEq, Debug, Clone)]
+pub struct Query {
+    pub last_name: String,
+    pub first_name: String,
+    pub middle_name: String,
+    pub gender: GenderChoice,
+    pub case: Case,
+}
+
+impl Query {
+    pub fn name(&self) -> FullName {
+        FullName::new(&self.last_name, &self.first_name, &self.middle_name)
+    }
+
+    pub fn gender(&self) -> Gender {
+        self.gender.gender(&self.middle_name)
+    }
+
+    //Every entry is inflected with the rules of its name part, empty entries are skipped
+    pub fn inflect(&self, petrovich: &Petrovich) -> FullName {
+        petrovich.full_name(&self.name(), &self.gender(), &self.case)
+    }
+
+    pub fn part_value(&self, part: &NamePart) -> &str {
+        match part {
+            NamePart::LastName => &self.last_name,
+            NamePart::FirstName => &self.first_name,
+            NamePart::MiddleName => &self.middle_name,
+        }
+    }
+
+    //Current forms of an entry from the genitive case on, to prefill the override editor
+    pub fn forms(&self, petrovich: &Petrovich, part: &NamePart) -> Vec<String> {
+        let value = self.part_value(part).trim();
+        let gender = self.gender();
+        CASES[1..]
+            .iter()
+            .map(|case| petrovich.inflect(part, value, &gender, case))
+            .collect()
+    }
+
+    //Words of every entry with the rules which inflected them
+    pub fn details(&self, petrovich: &Petrovich) -> Vec<(NamePart, WordInflection)> {
+        let name = self.name();
+        let gender = self.gender();
+        let mut details = Vec::new();
+        for part in NamePart::values() {
+            if let Some(value) = name.get(&part) {
+                let inflection = petrovich.inflect_detailed(&part, value, &gender, &self.case);
+                details.extend(inflection.words.into_iter().map(|word| (part, word)));
+            }
+        }
+        details
+    }
+
+    //The name in all cases, the selected case is ignored
+    pub fn declension(&self, petrovich: &Petrovich) -> Vec<(Case, FullName)> {
+        let name = self.name();
+        let gender = self.gender();
+        CASES
+            .into_iter()
+            .map(|case| {
+                let inflected = petrovich.full_name(&name, &gender, &case);
+                (case, inflected)
+            })
+            .collect()
+    }
+}
+
+//Items of the gender dropdown of the override editor
+pub const OVERRIDE_GENDERS: [Gender; 3] = [Gender::Male, Gender::Female, Gender::Androgynous];
+
+//Override of the editor: name part and gender are dropdown items, forms start from the genitive case
+pub fn override_of(
+    part: u32,
+    gender: u32,
+    name: &str,
+    forms: &[String],
+) -> Result<Override, Box<dyn Error>> {
+    let part = NamePart::values()
+        .get(part as usize)
+        .copied()
+        .ok_or_else(|| gettext("Unknown name part"))?;
+    let gender = OVERRIDE_GENDERS
+        .get(gender as usize)
+        .copied()
+        .ok_or_else(|| gettext("Unknown gender"))?;
+    let forms: Vec<&str> = forms.iter().map(String::as_str).collect();
+    let forms: [&str; 5] = forms
+        .as_slice()
+        .try_into()
+        .map_err(|_| gettext("Five forms are expected"))?;
+    Ok(Override::new(part, gender, name, forms)?)
+}
+
+//Title of a name part in the window
+pub fn part_title(part: &NamePart) -> String {
+    match part {
+        NamePart::LastName => gettext("Last name"),
+        NamePart::FirstName => gettext("First name"),
+        NamePart::MiddleName => gettext("Patronimic name"),
+    }
+}
+
+//A line of the overrides list: "Last name «жук», Female: жука, жуку, жука, жуком, жуке"
+pub fn override_title(o: &Override) -> String {
+    let forms: Vec<&str> = CASES[1..].iter().map(|case| o.form(case)).collect();
+    format!(
+        "{} «{}», {}: {}",
+        part_title(&o.part()),
+        o.name(),
+        gender_title(&o.gender()),
+        forms.join(", ")
+    )
+}
+
+//A line of the details panel: "Last name «иванову»: suffix «ов» of lastname.suffixes[0], male, mods: а, у, а, ым, е"
+pub fn describe(part: &NamePart, word: &WordInflection) -> String {
+    let title = format!("{} «{}»", part_title(part), word.value);
+    let kind = match word.kind {
+        MatchKind::Exception => gettext("exception"),
+        MatchKind::Suffix => gettext("suffix"),
+        MatchKind::Indeclinable => gettext("indeclinable"),
+        MatchKind::NoRule => return tr!("{}: no rule, kept unchanged", title),
+    };
+    match &word.rule {
+        Some(rule) => tr!(
+            "{}: {} «{}» of {}[{}], {}, mods: {}",
+            title,
+            kind,
+            word.test.as_deref().unwrap_or_default(),
+            rule.section,
+            rule.index,
+            rule.gender,
+            rule.mods.join(", ")
+        ),
+        None => format!("{}: {}", title, kind),
+    }
+}
+
+//Tab separated declension table for the clipboard, one case per line
+pub fn declension_text(declension: &[(Case, FullName)]) -> String {
+    let lines: Vec<String> = declension
+        .iter()
+        .map(|(case, name)| {
+            let parts = [&name.last_name, &name.first_name, &name.middle_name]
+                .map(|part| part.as_deref().unwrap_or_default());
+            format!("{}\t{}", case_title(case), parts.join("\t"))
+        })
+        .collect();
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use petrovich_core::overlay::Overlay;
+
+    fn test_petrovich() -> Petrovich {
+        "
+firstname:
+  suffixes:
+    - gender: male
+      test: [н]
+      mods: [а, у, а, ом, е]
+    - gender: female
+      test: [на]
+      mods: [-ы, -е, -у, -ой, -е]
+lastname:
+  suffixes:
+    - gender: female
+      test: [ова]
+      mods: [-ой, -ой, -у, -ой, -ой]
+    - gender: male
+      test: [ов]
+      mods: [а, у, а, ым, е]
+middlename:
+  suffixes:
+    - gender: male
+      test: [ич]
+      mods: [а, у, а, ем, е]
+    - gender: female
+      test: [на]
+      mods: [-ы, -е, -у, -ой, -е]
+"
+        .parse()
+        .unwrap()
+    }
+
+    fn query(last_name: &str, first_name: &str, middle_name: &str, gender: GenderChoice) -> Query {
+        Query {
+            last_name: last_name.to_string(),
+            first_name: first_name.to_string(),
+            middle_name: middle_name.to_string(),
+            gender,
+            case: Case::Dative,
+        }
+    }
+
+    #[test]
+    fn inflect_parts_test() {
+        let petrovich = test_petrovich();
+        // "н" is a first name suffix only, so the last name must not take it
+        let actual = query("Иванов", "Иван", "Иванович", GenderChoice::Male).inflect(&petrovich);
+        assert_eq!(FullName::new("Иванову", "Ивану", "Ивановичу"), actual);
+        let actual = query("", "Иван", "", GenderChoice::Male).inflect(&petrovich);
+        assert_eq!(FullName::new("", "Ивану", ""), actual);
+    }
+
+    #[test]
+    fn auto_gender_test() {
+        let petrovich = test_petrovich();
+        let actual = query("Иванова", "Анна", "Петровна", GenderChoice::Auto);
+        assert_eq!(Gender::Female, actual.gender());
+        assert_eq!(
+            FullName::new("Ивановой", "Анне", "Петровне"),
+            actual.inflect(&petrovich)
+        );
+        let actual = query("ИВАНОВ", "ИВАН", "ИВАНОВИЧ", GenderChoice::Auto);
+        assert_eq!(Gender::Male, actual.gender());
+        assert_eq!(
+            Gender::Androgynous,
+            query("", "", "", GenderChoice::Auto).gender()
+        );
+        assert_eq!(Gender::Male, query("", "", "", GenderChoice::Male).gender());
+    }
+
+    #[test]
+    fn declension_test() {
+        let petrovich = test_petrovich();
+        let declension = query("Иванов", "Иван", "", GenderChoice::Auto).declension(&petrovich);
+        assert_eq!(6, declension.len());
+        assert_eq!(
+            (Case::Instrumental, FullName::new("Ивановым", "Иваном", "")),
+            declension[4]
+        );
+        let actual = declension_text(&declension[..2]);
+        assert_eq!(
+            "Nominative\tИванов\tИван\t\nGenitive\tИванова\tИвана\t",
+            actual
+        );
+    }
+
+    #[test]
+    fn details_test() {
+        let petrovich = test_petrovich();
+        let query = query("Иванов-Шмидт", "Иван", "", GenderChoice::Male);
+        let actual: Vec<String> = query
+            .details(&petrovich)
+            .iter()
+            .map(|(part, word)| describe(part, word))
+            .collect();
+        assert_eq!(
+            vec![
+                "Last name «иванову»: suffix «ов» of lastname.suffixes[1], male, mods: а, у, а, ым, е",
+                "Last name «шмидт»: no rule, kept unchanged",
+                "First name «ивану»: suffix «н» of firstname.suffixes[0], male, mods: а, у, а, ом, е",
+            ],
+            actual
+        );
+        let (_, word) = &query.details(&petrovich)[0];
+        assert_eq!(Some(15), word.rule.as_ref().unwrap().line);
+    }
+
+    #[test]
+    fn override_test() {
+        let mut petrovich = test_petrovich();
+        let query = query("Жук", "", "", GenderChoice::Female);
+        let forms = query.forms(&petrovich, &NamePart::LastName);
+        assert_eq!(vec!["жук"; 5], forms);
+
+        let forms: Vec<String> = ["жука", "жуку", "жука", "жуком", "жуке"]
+            .map(str::to_string)
+            .to_vec();
+        let actual = override_of(0, 1, "Жук", &forms).unwrap();
+        assert_eq!(Gender::Female, actual.gender());
+        assert_eq!(
+            "Last name «жук», Female: жука, жуку, жука, жуком, жуке",
+            override_title(&actual)
+        );
+        let mut overlay = Overlay::default();
+        overlay.add(actual);
+        petrovich.set_overlay(&overlay);
+        assert_eq!(FullName::new("Жуку", "", ""), query.inflect(&petrovich));
+
+        let actual = override_of(0, 0, "Жук", &forms[..2]).unwrap_err();
+        assert_eq!("Five forms are expected", actual.to_string());
+        let actual = override_of(3, 0, "Жук", &forms).unwrap_err();
+        assert_eq!("Unknown name part", actual.to_string());
+    }
+
+    #[test]
+    fn dropdown_items_test() {
+        assert_eq!(Some(GenderChoice::Auto), GenderChoice::of(0));
+        assert_eq!(Some(GenderChoice::Androgynous), GenderChoice::of(3));
+        assert_eq!(None, GenderChoice::of(4));
+        assert_eq!(Some(Case::Nominative), case_of(0));
+        assert_eq!(Some(Case::Prepositional), case_of(5));
+        assert_eq!(None, case_of(6));
+        assert_eq!(3, GenderChoice::Androgynous.index());
+        assert_eq!(4, case_index(&Case::Instrumental));
+        assert_eq!(1, cycle(0, 6, 1));
+        assert_eq!(5, cycle(0, 6, -1));
+        assert_eq!(0, cycle(5, 6, 1));
+        assert_eq!(0, cycle(0, 0, 1));
+        let titles = GenderChoice::values().map(|choice| choice.title());
+        assert_eq!(["Auto", "Male", "Female", "Androgynous"], titles);
+        assert_eq!("Instrumental", case_title(&Case::Instrumental));
+    }
+}
@@ -0,0 +1,18 @@
+use std::cell::RefCell;
+
+use gtk::glib;
+use gtk::subclass::prelude::*;
+
+#[derive(Default)]
+pub struct TableRow {
+    pub cells: RefCell<Vec<String>>,
+}
+
+#[glib::object_subclass]
+impl ObjectSubclass for TableRow {
+    const NAME: &'static str = "MyGtkAppTableRow";
+    type Type = super::TableRow;
+    type ParentType = glib::Object;
+}
+
+impl ObjectImpl for TableRow {}
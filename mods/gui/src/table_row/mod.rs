@@ -0,0 +1,41 @@
+mod imp;
+
+use glib::Object;
+use gtk::glib;
+use gtk::subclass::prelude::*;
+use petrovich_core::case::Case;
+use petrovich_core::name::FullName;
+
+use crate::query;
+
+// Row of a `ColumnView` with text cells
+glib::wrapper! {
+    pub struct TableRow(ObjectSubclass<imp::TableRow>);
+}
+
+impl TableRow {
+    pub fn new(cells: Vec<String>) -> Self {
+        let row: Self = Object::new(&[]).expect("Failed to create `TableRow`.");
+        row.imp().cells.replace(cells);
+        row
+    }
+
+    // case, last name, first name, middle name
+    pub fn declension(case: &Case, name: &FullName) -> Self {
+        let mut cells = vec![query::case_title(case)];
+        cells.extend(
+            [&name.last_name, &name.first_name, &name.middle_name]
+                .map(|part| part.clone().unwrap_or_default()),
+        );
+        TableRow::new(cells)
+    }
+
+    pub fn cell(&self, column: usize) -> String {
+        self.imp()
+            .cells
+            .borrow()
+            .get(column)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
@@ -1,13 +1,38 @@
+use std::cell::RefCell;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use gettextrs::gettext;
 use glib::subclass::InitializingObject;
-use gtk::{prelude::*, Entry, Label, DropDown};
 use gtk::subclass::prelude::*;
-use gtk::{glib, CompositeTemplate};
+use gtk::{gio, glib, CompositeTemplate, FileChooserAction, FileChooserNative, ResponseType};
+use gtk::{
+    prelude::*, Button, CheckButton, ColumnView, DropDown, Entry, InfoBar, Label, ProgressBar,
+};
 use once_cell::sync::OnceCell;
-use petrovich_core::Petrovich;
 use petrovich_core::case::Case;
-use petrovich_core::gender::Gender;
+use petrovich_core::name::NamePart;
+use petrovich_core::overlay::{Overlay, Override};
+use petrovich_core::{Petrovich, EMBEDDED_RULES};
 
+use crate::batch::{self, BatchJob, Table};
+use crate::config::Config;
 use crate::custom_button::CustomButton;
+use crate::history::{self, History};
+use crate::i18n::tr;
+use crate::query::{self, GenderChoice, Query};
+use crate::table_row::TableRow;
+
+// Messages of the export thread
+enum BatchMessage {
+    Progress(usize),
+    Cancelled,
+    Finished(Result<(), String>),
+}
 
 #[derive(CompositeTemplate, Default)]
 #[template(resource = "/com/man/petrovich/window.ui")]
@@ -24,7 +49,50 @@ pub struct Window {
     pub case: TemplateChild<DropDown>,
     #[template_child]
     pub content: TemplateChild<Label>,
-    petrovich: OnceCell<Petrovich>,
+    #[template_child]
+    pub declension: TemplateChild<ColumnView>,
+    #[template_child]
+    pub details: TemplateChild<gtk::ListBox>,
+    #[template_child]
+    pub banner: TemplateChild<InfoBar>,
+    #[template_child]
+    pub banner_text: TemplateChild<Label>,
+    #[template_child]
+    pub batch_file: TemplateChild<Label>,
+    #[template_child]
+    pub batch_last_name: TemplateChild<DropDown>,
+    #[template_child]
+    pub batch_first_name: TemplateChild<DropDown>,
+    #[template_child]
+    pub batch_middle_name: TemplateChild<DropDown>,
+    #[template_child]
+    pub batch_gender: TemplateChild<DropDown>,
+    #[template_child]
+    pub batch_cases: TemplateChild<gtk::Box>,
+    #[template_child]
+    pub batch_preview: TemplateChild<ColumnView>,
+    #[template_child]
+    pub batch_progress: TemplateChild<ProgressBar>,
+    #[template_child]
+    pub batch_cancel: TemplateChild<Button>,
+    #[template_child]
+    pub batch_export: TemplateChild<Button>,
+    // shared with the export thread
+    petrovich: RefCell<Option<Arc<Petrovich>>>,
+    // content of the loaded rules file for the details panel
+    rules_source: RefCell<String>,
+    // user overrides applied on top of the rules
+    overlay: RefCell<Overlay>,
+    // list of the overrides window while it is open
+    overrides_list: RefCell<Option<gtk::ListBox>>,
+    history: RefCell<History>,
+    rows: OnceCell<gio::ListStore>,
+    batch_table: RefCell<Option<Table>>,
+    batch_rows: OnceCell<gio::ListStore>,
+    batch_case_buttons: OnceCell<Vec<CheckButton>>,
+    batch_cancelled: RefCell<Option<Arc<AtomicBool>>>,
+    // Native dialogs are not kept alive by GTK
+    chooser: RefCell<Option<FileChooserNative>>,
 }
 
 #[glib::object_subclass]
@@ -51,44 +119,864 @@ impl ObjectSubclass for Window {
 impl Window {
     #[template_callback]
     fn handle_button_clicked(&self) {
-        let pv = self.petrovich.get().unwrap();
-
-        let gender = self.gender.selected();
-        let gender = match gender {
-            0 => Gender::Male,
-            1 => Gender::Female,
-            2 => Gender::Androgynous,
-            _ => unreachable!()
+        self.update();
+        self.remember_query();
+    }
+
+    #[template_callback]
+    fn handle_input_changed(&self) {
+        self.update();
+    }
+
+    #[template_callback]
+    fn handle_banner_response(&self) {
+        self.banner.set_revealed(false);
+    }
+
+    #[template_callback]
+    fn handle_batch_changed(&self) {
+        self.update_batch_preview();
+    }
+
+    #[template_callback]
+    fn handle_batch_export(&self) {
+        self.export_batch(&self.instance());
+    }
+
+    #[template_callback]
+    fn handle_batch_cancel(&self) {
+        if let Some(cancelled) = self.batch_cancelled.borrow().as_ref() {
+            cancelled.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+// Every cell is a flat button copying its text
+fn append_columns(view: &ColumnView, titles: &[String]) {
+    for (index, title) in titles.iter().enumerate() {
+        let factory = gtk::SignalListItemFactory::new();
+        factory.connect_setup(|_, item| {
+            let button = gtk::Button::new();
+            button.set_has_frame(false);
+            button.set_tooltip_text(Some(&gettext("Copy")));
+            button.connect_clicked(|button| {
+                if let Some(text) = button.label() {
+                    button.clipboard().set_text(&text);
+                }
+            });
+            item.set_child(Some(&button));
+        });
+        factory.connect_bind(move |_, item| {
+            let row = item.item().unwrap().downcast::<TableRow>().unwrap();
+            let button = item.child().unwrap().downcast::<gtk::Button>().unwrap();
+            button.set_label(&row.cell(index));
+        });
+        let column = gtk::ColumnViewColumn::new(Some(title), Some(&factory));
+        column.set_expand(true);
+        view.append_column(&column);
+    }
+}
+
+fn remove_columns(view: &ColumnView) {
+    let columns = view.columns();
+    while let Some(column) = columns.item(0) {
+        view.remove_column(&column.downcast::<gtk::ColumnViewColumn>().unwrap());
+    }
+}
+
+fn table_model(view: &ColumnView) -> gio::ListStore {
+    let rows = gio::ListStore::new(TableRow::static_type());
+    let selection = gtk::NoSelection::new(Some(&rows));
+    view.set_model(Some(&selection));
+    rows
+}
+
+impl Window {
+    fn query(&self) -> Query {
+        Query {
+            last_name: self.last_name.text().to_string(),
+            first_name: self.first_name.text().to_string(),
+            middle_name: self.patronimic_name.text().to_string(),
+            gender: GenderChoice::of(self.gender.selected()).unwrap(),
+            case: query::case_of(self.case.selected()).unwrap(),
+        }
+    }
+
+    // Called on every edit, the rows are replaced as a whole
+    fn update(&self) {
+        // Signals of the template fire before `constructed`
+        let petrovich = self.petrovich.borrow();
+        let (pv, rows) = match (petrovich.as_ref(), self.rows.get()) {
+            (Some(pv), Some(rows)) => (pv, rows),
+            _ => return,
         };
-        let case = self.case.selected();
-        let case = match case {
-            0 => Case::Nominative,
-            1 => Case::Genitive,
-            2 => Case::Dative,
-            3 => Case::Accusative,
-            4 => Case::Instrumental,
-            5 => Case::Prepositional,
-            _ => unreachable!(),
+        let query = self.query();
+        self.content.set_text(&query.inflect(pv).to_string());
+
+        rows.remove_all();
+        for (case, name) in query.declension(pv) {
+            rows.append(&TableRow::declension(&case, &name));
+        }
+
+        while let Some(child) = self.details.first_child() {
+            self.details.remove(&child);
+        }
+        let window = self.instance();
+        for (part, word) in query.details(pv) {
+            let row = gtk::Box::new(gtk::Orientation::Horizontal, 6);
+            let label = Label::new(Some(&query::describe(&part, &word)));
+            label.set_hexpand(true);
+            label.set_wrap(true);
+            label.set_xalign(0.0);
+            row.append(&label);
+            if let Some(line) = word.rule.and_then(|rule| rule.line) {
+                let button = Button::with_label(&tr!("Line {}", line));
+                button.set_tooltip_text(Some(&gettext("Show the rule in the rules file")));
+                button.connect_clicked(glib::clone!(@weak window => move |_| {
+                    window.imp().show_rule(line);
+                }));
+                row.append(&button);
+            }
+            self.details.append(&row);
+        }
+    }
+
+    // Opens the loaded rules file with the line of the rule selected
+    fn show_rule(&self, line: usize) {
+        let buffer = gtk::TextBuffer::new(None);
+        buffer.set_text(&self.rules_source.borrow());
+        let view = gtk::TextView::with_buffer(&buffer);
+        view.set_editable(false);
+        view.set_monospace(true);
+        if let Some(start) = buffer.iter_at_line(line as i32 - 1) {
+            let mut end = start.clone();
+            end.forward_to_line_end();
+            buffer.select_range(&start, &end);
+            let mark = buffer.create_mark(None, &start, true);
+            view.scroll_to_mark(&mark, 0.0, true, 0.0, 0.3);
+        }
+        let scrolled = gtk::ScrolledWindow::builder().child(&view).build();
+        let window = gtk::Window::builder()
+            .title(&tr!("Rules, line {}", line))
+            .transient_for(&self.instance())
+            .default_width(600)
+            .default_height(500)
+            .child(&scrolled)
+            .build();
+        window.present();
+    }
+
+    // Items are generated from `GenderChoice` and `Case` with localized names
+    fn setup_dropdowns(&self) {
+        let genders = GenderChoice::values().map(|choice| choice.title());
+        let genders = genders.each_ref().map(String::as_str);
+        self.gender.set_model(Some(&gtk::StringList::new(&genders)));
+        self.batch_gender
+            .set_model(Some(&gtk::StringList::new(&genders)));
+        let cases = query::CASES.each_ref().map(query::case_title);
+        self.case.set_model(Some(&gtk::StringList::new(
+            &cases.each_ref().map(String::as_str),
+        )));
+    }
+
+    fn setup_declension(&self) {
+        let mut titles = vec![gettext("Case")];
+        titles.extend(NamePart::values().iter().map(query::part_title));
+        append_columns(&self.declension, &titles);
+        self.rows
+            .set(table_model(&self.declension))
+            .expect("Setting up declension rows");
+    }
+
+    fn setup_batch(&self, window: &super::Window) {
+        self.batch_rows
+            .set(table_model(&self.batch_preview))
+            .expect("Setting up batch rows");
+        let buttons: Vec<CheckButton> = query::CASES
+            .iter()
+            .map(|case| {
+                let button = CheckButton::with_label(&query::case_title(case));
+                button.set_active(*case == Case::Genitive);
+                button.connect_toggled(glib::clone!(@weak window => move |_| {
+                    window.imp().update_batch_preview();
+                }));
+                self.batch_cases.append(&button);
+                button
+            })
+            .collect();
+        self.batch_case_buttons
+            .set(buttons)
+            .expect("Setting up batch cases");
+    }
+
+    fn batch_job(&self, table: &Table) -> BatchJob {
+        let column = |dropdown: &DropDown| batch::column_of(dropdown.selected(), table);
+        let buttons = self
+            .batch_case_buttons
+            .get()
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        BatchJob {
+            last_name: column(&self.batch_last_name),
+            first_name: column(&self.batch_first_name),
+            middle_name: column(&self.batch_middle_name),
+            gender: GenderChoice::of(self.batch_gender.selected()).unwrap_or(GenderChoice::Auto),
+            cases: query::CASES
+                .into_iter()
+                .zip(buttons)
+                .filter(|(_, button)| button.is_active())
+                .map(|(case, _)| case)
+                .collect(),
+        }
+    }
+
+    pub fn open_batch(&self, window: &super::Window) {
+        let chooser = FileChooserNative::new(
+            Some(&gettext("Open CSV file")),
+            Some(window),
+            FileChooserAction::Open,
+            Some(&gettext("Open")),
+            Some(&gettext("Cancel")),
+        );
+        let filter = gtk::FileFilter::new();
+        filter.set_name(Some(&gettext("CSV and TSV files")));
+        filter.add_pattern("*.csv");
+        filter.add_pattern("*.tsv");
+        chooser.add_filter(&filter);
+
+        chooser.connect_response(glib::clone!(@weak window => move |chooser, response| {
+            let imp = window.imp();
+            if response == ResponseType::Accept {
+                if let Some(path) = chooser.file().and_then(|file| file.path()) {
+                    imp.load_batch_file(&path);
+                }
+            }
+            imp.chooser.replace(None);
+        }));
+        chooser.show();
+        self.chooser.replace(Some(chooser));
+    }
+
+    fn load_batch_file(&self, path: &Path) {
+        let table = match Table::load_from_file(path) {
+            Ok(table) => table,
+            Err(e) => {
+                self.show_error(&tr!("Failed to read {}: {}", path.display(), e));
+                return;
+            }
+        };
+        self.batch_file.set_text(&path.display().to_string());
+        let mut items = vec![batch::NO_COLUMN];
+        items.extend(table.headers.iter().map(String::as_str));
+        let columns = [
+            (&self.batch_last_name, batch::LAST_NAME_HEADERS),
+            (&self.batch_first_name, batch::FIRST_NAME_HEADERS),
+            (&self.batch_middle_name, batch::MIDDLE_NAME_HEADERS),
+        ];
+        // The table is set after the models, so the preview is built once
+        self.batch_table.replace(None);
+        for (dropdown, headers) in columns {
+            dropdown.set_model(Some(&gtk::StringList::new(&items)));
+            dropdown.set_selected(batch::guess_column(&table, headers));
+        }
+        self.batch_table.replace(Some(table));
+        self.batch_progress.set_fraction(0.0);
+        self.batch_progress.set_text(None);
+        self.batch_export.set_sensitive(true);
+        self.update_batch_preview();
+    }
+
+    fn update_batch_preview(&self) {
+        let (table, rows) = match (self.batch_table.borrow().as_ref(), self.batch_rows.get()) {
+            (Some(table), Some(rows)) => (table.head(batch::PREVIEW_ROWS), rows.clone()),
+            _ => return,
         };
+        let petrovich = self.petrovich.borrow();
+        let pv = match petrovich.as_ref() {
+            Some(pv) => pv,
+            None => return,
+        };
+        let preview = self
+            .batch_job(&table)
+            .run(pv, &table, &AtomicBool::new(false), |_| {})
+            .unwrap_or_default();
+
+        rows.remove_all();
+        remove_columns(&self.batch_preview);
+        append_columns(&self.batch_preview, &preview.headers);
+        for row in preview.rows {
+            rows.append(&TableRow::new(row));
+        }
+    }
 
-        let first_name = pv.first_name(self.first_name.text().as_str(), &gender, &case);
-        let last_name = pv.first_name(self.last_name.text().as_str(), &gender, &case);
-        let patronimic_name = pv.first_name(self.patronimic_name.text().as_str(), &gender, &case);
+    fn export_batch(&self, window: &super::Window) {
+        let chooser = FileChooserNative::new(
+            Some(&gettext("Export CSV file")),
+            Some(window),
+            FileChooserAction::Save,
+            Some(&gettext("Export")),
+            Some(&gettext("Cancel")),
+        );
+        chooser.set_current_name("result.csv");
 
-        let res = vec![last_name, first_name, patronimic_name].join(" ");
-        self.content.set_text(&res);
+        chooser.connect_response(glib::clone!(@weak window => move |chooser, response| {
+            let imp = window.imp();
+            if response == ResponseType::Accept {
+                if let Some(path) = chooser.file().and_then(|file| file.path()) {
+                    imp.start_export(&window, path);
+                }
+            }
+            imp.chooser.replace(None);
+        }));
+        chooser.show();
+        self.chooser.replace(Some(chooser));
+    }
+
+    // Inflects the whole table in a thread, the progress is sent back to the main loop
+    fn start_export(&self, window: &super::Window, path: PathBuf) {
+        let (pv, table) = match (
+            self.petrovich.borrow().clone(),
+            self.batch_table.borrow().clone(),
+        ) {
+            (Some(pv), Some(table)) => (pv, table),
+            _ => return,
+        };
+        let job = self.batch_job(&table);
+        let total = table.rows.len();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        self.batch_cancelled.replace(Some(cancelled.clone()));
+        self.batch_export.set_sensitive(false);
+        self.batch_cancel.set_sensitive(true);
+        self.batch_progress.set_fraction(0.0);
+        self.batch_progress
+            .set_text(Some(&tr!("{} of {}", 0, total)));
+
+        let (sender, receiver) = glib::MainContext::channel(glib::PRIORITY_DEFAULT);
+        thread::spawn(move || {
+            let result = job.run(&pv, &table, &cancelled, |done| {
+                if done % 100 == 0 || done == total {
+                    let _ = sender.send(BatchMessage::Progress(done));
+                }
+            });
+            let message = match result {
+                None => BatchMessage::Cancelled,
+                Some(result) => {
+                    BatchMessage::Finished(result.save_to_file(&path).map_err(|e| e.to_string()))
+                }
+            };
+            let _ = sender.send(message);
+        });
+
+        receiver.attach(None, glib::clone!(@weak window => @default-return glib::Continue(false),
+            move |message| {
+                let imp = window.imp();
+                match message {
+                    BatchMessage::Progress(done) => {
+                        imp.batch_progress.set_fraction(done as f64 / total.max(1) as f64);
+                        imp.batch_progress.set_text(Some(&tr!("{} of {}", done, total)));
+                        return glib::Continue(true);
+                    }
+                    BatchMessage::Cancelled => imp.batch_progress.set_text(Some(&gettext("Cancelled"))),
+                    BatchMessage::Finished(Ok(())) => imp.batch_progress.set_text(Some(&gettext("Exported"))),
+                    BatchMessage::Finished(Err(e)) => {
+                        imp.batch_progress.set_text(None);
+                        imp.show_error(&tr!("Failed to export: {}", e));
+                    }
+                }
+                imp.batch_cancelled.replace(None);
+                imp.batch_export.set_sensitive(true);
+                imp.batch_cancel.set_sensitive(false);
+                glib::Continue(false)
+            }));
+    }
+
+    pub fn copy_all(&self) {
+        if let Some(pv) = self.petrovich.borrow().as_ref() {
+            let text = query::declension_text(&self.query().declension(pv));
+            self.declension.clipboard().set_text(&text);
+        }
+    }
+
+    // Ctrl+C keeps copying the selected text of an entry
+    pub fn copy_result(&self, window: &super::Window) {
+        let text = window
+            .focus_widget()
+            .and_then(|widget| widget.downcast::<gtk::Text>().ok());
+        match text {
+            Some(text) if text.selection_bounds().is_some() => text.emit_copy_clipboard(),
+            _ => self.content.clipboard().set_text(&self.content.text()),
+        }
+    }
+
+    pub fn cycle_case(&self, step: i32) {
+        cycle_dropdown(&self.case, step);
+    }
+
+    pub fn cycle_gender(&self, step: i32) {
+        cycle_dropdown(&self.gender, step);
+    }
+
+    fn load_history(&self) {
+        self.history
+            .replace(History::load_from_str(&config().history()));
+    }
+
+    fn remember_query(&self) {
+        let mut history = self.history.borrow_mut();
+        history.add(&self.query());
+        if let Err(e) = config().set_history(&history.to_text()) {
+            self.show_error(&tr!("Failed to save settings: {}", e));
+        }
+    }
+
+    // Sets the entries and dropdowns, the name is inflected by their signals
+    fn restore(&self, query: &Query) {
+        self.last_name.set_text(&query.last_name);
+        self.first_name.set_text(&query.first_name);
+        self.patronimic_name.set_text(&query.middle_name);
+        self.gender.set_selected(query.gender.index());
+        self.case.set_selected(query::case_index(&query.case));
+    }
+
+    // Recent queries filtered by a search entry, Enter restores the first match
+    pub fn show_history(&self, window: &super::Window) {
+        let search = gtk::SearchEntry::new();
+        search.set_placeholder_text(Some(&gettext("Search history")));
+        search.update_property(&[gtk::accessible::Property::Label(&gettext("Search history"))]);
+        let list = gtk::ListBox::new();
+        list.update_property(&[gtk::accessible::Property::Label(&gettext("Recent queries"))]);
+        for query in self.history.borrow().queries() {
+            let label = Label::new(Some(&history::title(query)));
+            label.set_xalign(0.0);
+            list.append(&label);
+        }
+        list.set_placeholder(Some(&Label::new(Some(&gettext("No queries yet")))));
+        let scrolled = gtk::ScrolledWindow::builder()
+            .child(&list)
+            .vexpand(true)
+            .build();
+        let content = gtk::Box::builder()
+            .orientation(gtk::Orientation::Vertical)
+            .spacing(6)
+            .margin_top(12)
+            .margin_bottom(12)
+            .margin_start(12)
+            .margin_end(12)
+            .build();
+        content.append(&search);
+        content.append(&scrolled);
+        let dialog = gtk::Window::builder()
+            .title(&gettext("History"))
+            .transient_for(window)
+            .modal(true)
+            .default_width(400)
+            .default_height(400)
+            .child(&content)
+            .build();
+        search.set_key_capture_widget(Some(&dialog));
+
+        list.set_filter_func(
+            glib::clone!(@weak window, @weak search => @default-return true, move |row| {
+                let history = window.imp().history.borrow();
+                match history.queries().get(row.index() as usize) {
+                    Some(query) => history::matches(query, &search.text()),
+                    None => true,
+                }
+            }),
+        );
+        search
+            .connect_search_changed(glib::clone!(@weak list => move |_| list.invalidate_filter()));
+        search.connect_activate(glib::clone!(@weak window, @weak dialog => move |search| {
+            let query = window.imp().history.borrow().search(&search.text()).first().cloned().cloned();
+            if let Some(query) = query {
+                window.imp().restore(&query);
+                dialog.close();
+            }
+        }));
+        search.connect_stop_search(glib::clone!(@weak dialog => move |_| dialog.close()));
+        list.connect_row_activated(glib::clone!(@weak window, @weak dialog => move |_, row| {
+            let query = window.imp().history.borrow().queries().get(row.index() as usize).cloned();
+            if let Some(query) = query {
+                window.imp().restore(&query);
+                dialog.close();
+            }
+        }));
+        dialog.present();
+        search.grab_focus();
+    }
+
+    pub fn open_rules(&self, window: &super::Window) {
+        let chooser = FileChooserNative::new(
+            Some(&gettext("Open rules file")),
+            Some(window),
+            FileChooserAction::Open,
+            Some(&gettext("Open")),
+            Some(&gettext("Cancel")),
+        );
+        let filter = gtk::FileFilter::new();
+        filter.set_name(Some(&gettext("YAML rules")));
+        filter.add_pattern("*.yml");
+        filter.add_pattern("*.yaml");
+        chooser.add_filter(&filter);
+
+        chooser.connect_response(glib::clone!(@weak window => move |chooser, response| {
+            let imp = window.imp();
+            if response == ResponseType::Accept {
+                if let Some(path) = chooser.file().and_then(|file| file.path()) {
+                    imp.load_rules_file(&path);
+                }
+            }
+            imp.chooser.replace(None);
+        }));
+        chooser.show();
+        self.chooser.replace(Some(chooser));
+    }
+
+    // The last rules file of the previous run, the embedded rules otherwise
+    fn load_initial_rules(&self) {
+        match read_overlay() {
+            Ok(overlay) => {
+                self.overlay.replace(overlay);
+            }
+            Err(e) => self.show_error(&tr!("Failed to load overrides: {}", e)),
+        }
+        if let Err(e) = self.set_rules(EMBEDDED_RULES.to_string()) {
+            self.show_error(&tr!("Failed to load embedded rules: {}", e));
+        }
+        if let Some(path) = config().rules_file() {
+            self.load_rules_file(&path);
+        }
+    }
+
+    fn load_rules_file(&self, path: &Path) {
+        match self.read_rules(path) {
+            Ok(()) => {
+                self.banner.set_revealed(false);
+                if let Err(e) = config().set_rules_file(path) {
+                    self.show_error(&tr!("Failed to save settings: {}", e));
+                }
+            }
+            Err(e) => self.show_error(&tr!("Failed to load rules from {}: {}", path.display(), e)),
+        }
+        self.update();
+        self.update_batch_preview();
+    }
+
+    fn read_rules(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let source = fs::read_to_string(path)?;
+        self.set_rules(source)
+    }
+
+    // Parses the rules and applies the user overrides
+    fn set_rules(&self, source: String) -> Result<(), Box<dyn Error>> {
+        let mut pv: Petrovich = source.parse()?;
+        pv.set_overlay(&self.overlay.borrow());
+        self.petrovich.replace(Some(Arc::new(pv)));
+        self.rules_source.replace(source);
+        Ok(())
+    }
+
+    // Saves the overrides and inflects the name again
+    fn set_overlay(&self, overlay: Overlay) {
+        if let Err(e) = config().set_overrides(&overlay.to_yaml()) {
+            self.show_error(&tr!("Failed to save overrides: {}", e));
+        }
+        self.overlay.replace(overlay);
+        let source = self.rules_source.borrow().clone();
+        if let Err(e) = self.set_rules(source) {
+            self.show_error(&tr!("Failed to apply overrides: {}", e));
+        }
+        self.refresh_overrides();
+        self.update();
+        self.update_batch_preview();
+    }
+
+    fn add_override(&self, o: Override) {
+        let mut overlay = self.overlay.borrow().clone();
+        overlay.add(o);
+        self.set_overlay(overlay);
+    }
+
+    fn remove_override(&self, index: usize) {
+        let mut overlay = self.overlay.borrow().clone();
+        overlay.remove(index);
+        self.set_overlay(overlay);
+    }
+
+    // Editor of the forms of an entry, opened by a right click on the result
+    pub fn edit_override(&self, window: &super::Window) {
+        let query = self.query();
+        // Current forms of every entry, the editor is filled when the name part changes
+        let prefill: Vec<(String, Vec<String>)> = match self.petrovich.borrow().as_ref() {
+            Some(pv) => NamePart::values()
+                .iter()
+                .map(|part| {
+                    (
+                        query.part_value(part).trim().to_string(),
+                        query.forms(pv, part),
+                    )
+                })
+                .collect(),
+            None => return,
+        };
+
+        let (cancel, save) = (gettext("Cancel"), gettext("Save"));
+        let dialog = gtk::Dialog::with_buttons(
+            Some(&gettext("Correct inflection")),
+            Some(window),
+            gtk::DialogFlags::MODAL | gtk::DialogFlags::DESTROY_WITH_PARENT,
+            &[
+                (&cancel, ResponseType::Cancel),
+                (&save, ResponseType::Accept),
+            ],
+        );
+        let grid = gtk::Grid::builder()
+            .row_spacing(6)
+            .column_spacing(6)
+            .margin_top(12)
+            .margin_bottom(12)
+            .margin_start(12)
+            .margin_end(12)
+            .build();
+        let titles: Vec<&str> = NamePart::values().iter().map(query::part_title).collect();
+        let part = DropDown::from_strings(&titles);
+        let genders = query::OVERRIDE_GENDERS.map(|gender| query::gender_title(&gender));
+        let gender = DropDown::from_strings(&genders.each_ref().map(String::as_str));
+        let name = Entry::new();
+        let forms: Vec<Entry> = query::CASES[1..].iter().map(|_| Entry::new()).collect();
+        let mut rows = vec![
+            (gettext("Name part"), part.upcast_ref::<gtk::Widget>()),
+            (gettext("Gender"), gender.upcast_ref()),
+            (query::case_title(&Case::Nominative), name.upcast_ref()),
+        ];
+        for (case, entry) in query::CASES[1..].iter().zip(&forms) {
+            rows.push((query::case_title(case), entry.upcast_ref()));
+        }
+        for (row, (title, widget)) in rows.into_iter().enumerate() {
+            grid.attach(&Label::new(Some(&title)), 0, row as i32, 1, 1);
+            grid.attach(widget, 1, row as i32, 1, 1);
+        }
+        dialog.content_area().append(&grid);
+
+        let fill = glib::clone!(@weak name, @strong forms => move |index: u32| {
+            if let Some((value, values)) = prefill.get(index as usize) {
+                name.set_text(value);
+                for (entry, value) in forms.iter().zip(values) {
+                    entry.set_text(value);
+                }
+            }
+        });
+        let selected = prefill_part(&query);
+        fill(selected);
+        part.set_selected(selected);
+        part.connect_selected_notify(move |part| fill(part.selected()));
+        let selected = query::OVERRIDE_GENDERS
+            .iter()
+            .position(|g| *g == query.gender());
+        gender.set_selected(selected.unwrap_or_default() as u32);
+
+        dialog.connect_response(glib::clone!(@weak window, @weak part, @weak gender, @weak name => move |dialog, response| {
+            if response == ResponseType::Accept {
+                let values: Vec<String> = forms.iter().map(|entry| entry.text().to_string()).collect();
+                match query::override_of(part.selected(), gender.selected(), &name.text(), &values) {
+                    Ok(o) => window.imp().add_override(o),
+                    Err(e) => window.imp().show_error(&tr!("Failed to save the correction: {}", e)),
+                }
+            }
+            dialog.close();
+        }));
+        dialog.show();
+    }
+
+    pub fn show_overrides(&self, window: &super::Window) {
+        let list = gtk::ListBox::new();
+        list.set_selection_mode(gtk::SelectionMode::None);
+        let scrolled = gtk::ScrolledWindow::builder()
+            .child(&list)
+            .vexpand(true)
+            .build();
+        let import = Button::with_label(&gettext("Import…"));
+        let export = Button::with_label(&gettext("Export…"));
+        let buttons = gtk::Box::new(gtk::Orientation::Horizontal, 6);
+        buttons.set_halign(gtk::Align::End);
+        buttons.append(&import);
+        buttons.append(&export);
+        let content = gtk::Box::builder()
+            .orientation(gtk::Orientation::Vertical)
+            .spacing(6)
+            .margin_top(12)
+            .margin_bottom(12)
+            .margin_start(12)
+            .margin_end(12)
+            .build();
+        content.append(&scrolled);
+        content.append(&buttons);
+        let overrides = gtk::Window::builder()
+            .title(&gettext("Overrides"))
+            .transient_for(window)
+            .default_width(500)
+            .default_height(400)
+            .child(&content)
+            .build();
+
+        import.connect_clicked(glib::clone!(@weak window, @weak overrides => move |_| {
+            window.imp().import_overrides(&overrides);
+        }));
+        export.connect_clicked(glib::clone!(@weak window, @weak overrides => move |_| {
+            window.imp().export_overrides(&overrides);
+        }));
+        overrides.connect_close_request(
+            glib::clone!(@weak window => @default-return gtk::Inhibit(false), move |_| {
+                window.imp().overrides_list.replace(None);
+                gtk::Inhibit(false)
+            }),
+        );
+        self.overrides_list.replace(Some(list));
+        self.refresh_overrides();
+        overrides.present();
+    }
+
+    fn refresh_overrides(&self) {
+        let list = match self.overrides_list.borrow().clone() {
+            Some(list) => list,
+            None => return,
+        };
+        while let Some(child) = list.first_child() {
+            list.remove(&child);
+        }
+        let window = self.instance();
+        for (index, o) in self.overlay.borrow().overrides().iter().enumerate() {
+            let row = gtk::Box::new(gtk::Orientation::Horizontal, 6);
+            let label = Label::new(Some(&query::override_title(o)));
+            label.set_hexpand(true);
+            label.set_wrap(true);
+            label.set_xalign(0.0);
+            row.append(&label);
+            let remove = Button::with_label(&gettext("Remove"));
+            remove.connect_clicked(glib::clone!(@weak window => move |_| {
+                window.imp().remove_override(index);
+            }));
+            row.append(&remove);
+            list.append(&row);
+        }
+        if self.overlay.borrow().is_empty() {
+            list.append(&Label::new(Some(&gettext(
+                "Right-click the result to correct an inflection",
+            ))));
+        }
+    }
+
+    // Overrides of the file are added to the current ones
+    fn import_overrides(&self, parent: &gtk::Window) {
+        let window = self.instance();
+        let chooser = FileChooserNative::new(
+            Some(&gettext("Import overrides")),
+            Some(parent),
+            FileChooserAction::Open,
+            Some(&gettext("Import")),
+            Some(&gettext("Cancel")),
+        );
+        chooser.connect_response(glib::clone!(@weak window => move |chooser, response| {
+            let imp = window.imp();
+            if response == ResponseType::Accept {
+                if let Some(path) = chooser.file().and_then(|file| file.path()) {
+                    match read_overlay_file(&path) {
+                        Ok(imported) => {
+                            let mut overlay = imp.overlay.borrow().clone();
+                            overlay.extend(imported);
+                            imp.set_overlay(overlay);
+                        }
+                        Err(e) => imp.show_error(&tr!("Failed to import {}: {}", path.display(), e)),
+                    }
+                }
+            }
+            imp.chooser.replace(None);
+        }));
+        chooser.show();
+        self.chooser.replace(Some(chooser));
+    }
+
+    fn export_overrides(&self, parent: &gtk::Window) {
+        let window = self.instance();
+        let chooser = FileChooserNative::new(
+            Some(&gettext("Export overrides")),
+            Some(parent),
+            FileChooserAction::Save,
+            Some(&gettext("Export")),
+            Some(&gettext("Cancel")),
+        );
+        chooser.set_current_name("overrides.yml");
+        chooser.connect_response(glib::clone!(@weak window => move |chooser, response| {
+            let imp = window.imp();
+            if response == ResponseType::Accept {
+                if let Some(path) = chooser.file().and_then(|file| file.path()) {
+                    if let Err(e) = fs::write(&path, imp.overlay.borrow().to_yaml()) {
+                        imp.show_error(&tr!("Failed to export {}: {}", path.display(), e));
+                    }
+                }
+            }
+            imp.chooser.replace(None);
+        }));
+        chooser.show();
+        self.chooser.replace(Some(chooser));
+    }
+
+    fn show_error(&self, message: &str) {
+        self.banner_text.set_text(message);
+        self.banner.set_revealed(true);
     }
 }
 
+fn cycle_dropdown(dropdown: &DropDown, step: i32) {
+    let count = dropdown
+        .model()
+        .map(|model| model.n_items())
+        .unwrap_or_default();
+    dropdown.set_selected(query::cycle(dropdown.selected(), count, step));
+}
+
+// The first filled entry
+fn prefill_part(query: &Query) -> u32 {
+    NamePart::values()
+        .iter()
+        .position(|part| !query.part_value(part).trim().is_empty())
+        .unwrap_or_default() as u32
+}
+
+fn read_overlay() -> Result<Overlay, Box<dyn Error>> {
+    match config().overrides()? {
+        Some(content) => Overlay::load_from_str(&content),
+        None => Ok(Overlay::default()),
+    }
+}
+
+fn read_overlay_file(path: &Path) -> Result<Overlay, Box<dyn Error>> {
+    Overlay::load_from_str(&fs::read_to_string(path)?)
+}
+
+fn config() -> Config {
+    Config::new(&glib::user_config_dir())
+}
+
 impl ObjectImpl for Window {
     fn constructed(&self, obj: &Self::Type) {
         self.parent_constructed(obj);
 
-        let pv = Petrovich::new("mods/core/petrovich-rules/rules.yml").expect("Loading petrovich rules");
-        self.petrovich.set(pv).expect("Setting up petrovich object");
+        self.setup_dropdowns();
+        self.load_history();
+        self.setup_declension();
+        self.setup_batch(obj);
+
+        let gesture = gtk::GestureClick::new();
+        gesture.set_button(3);
+        gesture.connect_pressed(glib::clone!(@weak obj => move |_, _, _, _| {
+            obj.imp().edit_override(&obj);
+        }));
+        self.content.add_controller(&gesture);
+        self.load_initial_rules();
+        self.update();
     }
 }
 impl WidgetImpl for Window {}
 impl WindowImpl for Window {}
 impl ApplicationWindowImpl for Window {}
-
@@ -1,6 +1,7 @@
 mod imp;
 
 use glib::Object;
+use gtk::subclass::prelude::*;
 use gtk::{gio, glib, Application};
 
 glib::wrapper! {
@@ -14,4 +15,40 @@ impl Window {
     pub fn new(app: &Application) -> Self {
         Object::new(&[("application", app)]).expect("Failed to create Window")
     }
+
+    pub fn copy_all(&self) {
+        self.imp().copy_all();
+    }
+
+    pub fn open_rules(&self) {
+        self.imp().open_rules(self);
+    }
+
+    pub fn open_batch(&self) {
+        self.imp().open_batch(self);
+    }
+
+    pub fn show_overrides(&self) {
+        self.imp().show_overrides(self);
+    }
+
+    pub fn edit_override(&self) {
+        self.imp().edit_override(self);
+    }
+
+    pub fn copy_result(&self) {
+        self.imp().copy_result(self);
+    }
+
+    pub fn show_history(&self) {
+        self.imp().show_history(self);
+    }
+
+    pub fn cycle_case(&self, step: i32) {
+        self.imp().cycle_case(step);
+    }
+
+    pub fn cycle_gender(&self, step: i32) {
+        self.imp().cycle_gender(step);
+    }
 }
@@ -0,0 +1,234 @@
+use petrovich_core::case::Case;
+use petrovich_core::gender::Gender;
+use petrovich_core::name::{FullName, NamePart};
+use petrovich_core::Petrovich;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+#[pyclass(
+    name = "Case",
+    module = "petrovich",
+    eq,
+    eq_int,
+    hash,
+    frozen,
+    from_py_object
+)]
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+enum PyCase {
+    #[pyo3(name = "NOMINATIVE")]
+    Nominative,
+    #[pyo3(name = "GENITIVE")]
+    Genitive,
+    #[pyo3(name = "DATIVE")]
+    Dative,
+    #[pyo3(name = "ACCUSATIVE")]
+    Accusative,
+    #[pyo3(name = "INSTRUMENTAL")]
+    Instrumental,
+    #[pyo3(name = "PREPOSITIONAL")]
+    Prepositional,
+}
+
+#[pyclass(
+    name = "Gender",
+    module = "petrovich",
+    eq,
+    eq_int,
+    hash,
+    frozen,
+    from_py_object
+)]
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+enum PyGender {
+    #[pyo3(name = "MALE")]
+    Male,
+    #[pyo3(name = "FEMALE")]
+    Female,
+    #[pyo3(name = "ANDROGYNOUS")]
+    Androgynous,
+}
+
+//A single gender for all names or a gender for every name
+#[derive(FromPyObject)]
+enum Genders {
+    One(PyGender),
+    Many(Vec<PyGender>),
+}
+
+#[pyclass(name = "Petrovich", module = "petrovich", frozen)]
+struct PyPetrovich {
+    inner: Petrovich,
+}
+
+#[pymethods]
+impl PyPetrovich {
+    //Loads rules from a YAML file
+    #[new]
+    fn new(rules_file: &str) -> PyResult<PyPetrovich> {
+        let inner = Petrovich::new(rules_file).map_err(to_py_error)?;
+        Ok(PyPetrovich { inner })
+    }
+
+    //Loads rules from a YAML string
+    #[staticmethod]
+    fn from_yaml(content: &str) -> PyResult<PyPetrovich> {
+        let inner = content.parse().map_err(to_py_error)?;
+        Ok(PyPetrovich { inner })
+    }
+
+    //Rules compiled into the module, available with the `embedded-rules` feature
+    #[staticmethod]
+    fn embedded() -> PyResult<PyPetrovich> {
+        #[cfg(feature = "embedded-rules")]
+        {
+            let inner = Petrovich::embedded().map_err(to_py_error)?;
+            Ok(PyPetrovich { inner })
+        }
+        #[cfg(not(feature = "embedded-rules"))]
+        Err(PyValueError::new_err(
+            "The module is built without embedded rules",
+        ))
+    }
+
+    fn inflect_first(&self, name: &str, gender: PyGender, case: PyCase) -> String {
+        self.inflect(&NamePart::FirstName, name, gender, case)
+    }
+
+    fn inflect_last(&self, name: &str, gender: PyGender, case: PyCase) -> String {
+        self.inflect(&NamePart::LastName, name, gender, case)
+    }
+
+    fn inflect_middle(&self, name: &str, gender: PyGender, case: PyCase) -> String {
+        self.inflect(&NamePart::MiddleName, name, gender, case)
+    }
+
+    //Keeps the letter case of the name, returns `(last_name, first_name, middle_name)`.
+    //Without `gender` it is detected by the middle name.
+    #[pyo3(signature = (last_name=None, first_name=None, middle_name=None, *, case, gender=None))]
+    fn inflect_full_name(
+        &self,
+        last_name: Option<&str>,
+        first_name: Option<&str>,
+        middle_name: Option<&str>,
+        case: PyCase,
+        gender: Option<PyGender>,
+    ) -> (Option<String>, Option<String>, Option<String>) {
+        let name = FullName::new(
+            last_name.unwrap_or_default(),
+            first_name.unwrap_or_default(),
+            middle_name.unwrap_or_default(),
+        );
+        let gender = gender.map_or_else(|| name.gender(), to_gender);
+        let result = self.inner.full_name(&name, &gender, &to_case(case));
+        (result.last_name, result.first_name, result.middle_name)
+    }
+
+    //Batch versions release the GIL while names are inflected
+    fn inflect_first_many(
+        &self,
+        py: Python<'_>,
+        names: Vec<String>,
+        gender: Genders,
+        case: PyCase,
+    ) -> PyResult<Vec<String>> {
+        self.inflect_many(py, &NamePart::FirstName, names, gender, case)
+    }
+
+    fn inflect_last_many(
+        &self,
+        py: Python<'_>,
+        names: Vec<String>,
+        gender: Genders,
+        case: PyCase,
+    ) -> PyResult<Vec<String>> {
+        self.inflect_many(py, &NamePart::LastName, names, gender, case)
+    }
+
+    fn inflect_middle_many(
+        &self,
+        py: Python<'_>,
+        names: Vec<String>,
+        gender: Genders,
+        case: PyCase,
+    ) -> PyResult<Vec<String>> {
+        self.inflect_many(py, &NamePart::MiddleName, names, gender, case)
+    }
+}
+
+impl PyPetrovich {
+    fn inflect(&self, part: &NamePart, name: &str, gender: PyGender, case: PyCase) -> String {
+        self.inner
+            .inflect(part, name, &to_gender(gender), &to_case(case))
+    }
+
+    fn inflect_many(
+        &self,
+        py: Python<'_>,
+        part: &NamePart,
+        names: Vec<String>,
+        gender: Genders,
+        case: PyCase,
+    ) -> PyResult<Vec<String>> {
+        let genders = match gender {
+            Genders::One(gender) => vec![gender; names.len()],
+            Genders::Many(genders) if genders.len() == names.len() => genders,
+            Genders::Many(genders) => {
+                return Err(PyValueError::new_err(format!(
+                    "Got {} genders for {} names",
+                    genders.len(),
+                    names.len()
+                )))
+            }
+        };
+        Ok(py.detach(|| {
+            names
+                .iter()
+                .zip(genders)
+                .map(|(name, gender)| self.inflect(part, name, gender, case))
+                .collect()
+        }))
+    }
+}
+
+//Gender by the middle name
+#[pyfunction]
+fn detect_gender(middle_name: &str) -> PyGender {
+    match Gender::detect_gender(middle_name) {
+        Gender::Male => PyGender::Male,
+        Gender::Female => PyGender::Female,
+        Gender::Androgynous => PyGender::Androgynous,
+    }
+}
+
+fn to_gender(gender: PyGender) -> Gender {
+    match gender {
+        PyGender::Male => Gender::Male,
+        PyGender::Female => Gender::Female,
+        PyGender::Androgynous => Gender::Androgynous,
+    }
+}
+
+fn to_case(case: PyCase) -> Case {
+    match case {
+        PyCase::Nominative => Case::Nominative,
+        PyCase::Genitive => Case::Genitive,
+        PyCase::Dative => Case::Dative,
+        PyCase::Accusative => Case::Accusative,
+        PyCase::Instrumental => Case::Instrumental,
+        PyCase::Prepositional => Case::Prepositional,
+    }
+}
+
+fn to_py_error(error: Box<dyn std::error::Error>) -> PyErr {
+    PyValueError::new_err(error.to_string())
+}
+
+#[pymodule]
+fn petrovich(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyCase>()?;
+    m.add_class::<PyGender>()?;
+    m.add_class::<PyPetrovich>()?;
+    m.add_function(wrap_pyfunction!(detect_gender, m)?)?;
+    Ok(())
+}
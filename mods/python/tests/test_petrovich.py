@@ -0,0 +1,109 @@
+import threading
+
+import pytest
+
+import petrovich
+from petrovich import Case, Gender, Petrovich
+
+RULES = """
+firstname:
+  exceptions:
+    - gender: male
+      test: [лев]
+      mods: [--ьва, --ьву, --ьва, --ьвом, --ьве]
+  suffixes:
+    - gender: male
+      test: [н]
+      mods: [а, у, а, ом, е]
+    - gender: female
+      test: [на]
+      mods: [-ы, -е, -у, -ой, -е]
+lastname:
+  suffixes:
+    - gender: female
+      test: [ова]
+      mods: [-ой, -ой, -у, -ой, -ой]
+    - gender: male
+      test: [ов]
+      mods: [а, у, а, ым, е]
+middlename:
+  suffixes:
+    - gender: male
+      test: [ич]
+      mods: [а, у, а, ем, е]
+    - gender: female
+      test: [на]
+      mods: [-ы, -е, -у, -ой, -е]
+"""
+
+
+@pytest.fixture(scope="module")
+def p():
+    return Petrovich.from_yaml(RULES)
+
+
+def test_inflect_parts(p):
+    assert p.inflect_first("Лев", Gender.MALE, Case.DATIVE) == "льву"
+    assert p.inflect_last("Иванов", Gender.MALE, Case.INSTRUMENTAL) == "ивановым"
+    assert p.inflect_middle("Петрович", Gender.MALE, Case.GENITIVE) == "петровича"
+    assert p.inflect_first("Иван", Gender.MALE, Case.NOMINATIVE) == "иван"
+
+
+def test_inflect_full_name(p):
+    actual = p.inflect_full_name("Иванова", "Анна", "Петровна", case=Case.DATIVE)
+    assert actual == ("Ивановой", "Анне", "Петровне")
+    actual = p.inflect_full_name(last_name="Иванов", first_name="Иван",
+                                 case=Case.GENITIVE, gender=Gender.MALE)
+    assert actual == ("Иванова", "Ивана", None)
+
+
+def test_inflect_many(p):
+    names = ["Иванов", "Петров", "Сидоров"]
+    actual = p.inflect_last_many(names, Gender.MALE, Case.DATIVE)
+    assert actual == ["иванову", "петрову", "сидорову"]
+    genders = [Gender.MALE, Gender.FEMALE]
+    actual = p.inflect_last_many(["Иванов", "Иванова"], genders, Case.GENITIVE)
+    assert actual == ["иванова", "ивановой"]
+    assert p.inflect_first_many([], Gender.MALE, Case.DATIVE) == []
+
+
+def test_inflect_many_threads(p):
+    names = ["Иван"] * 10000
+    results = []
+
+    def inflect():
+        results.append(p.inflect_first_many(names, Gender.MALE, Case.DATIVE))
+
+    threads = [threading.Thread(target=inflect) for _ in range(4)]
+    for thread in threads:
+        thread.start()
+    for thread in threads:
+        thread.join()
+    assert len(results) == 4
+    assert all(result == ["ивану"] * 10000 for result in results)
+
+
+def test_inflect_many_errors(p):
+    with pytest.raises(ValueError, match="Got 1 genders for 2 names"):
+        p.inflect_last_many(["Иванов", "Петров"], [Gender.MALE], Case.DATIVE)
+    with pytest.raises(TypeError):
+        p.inflect_last_many(["Иванов"], "male", Case.DATIVE)
+
+
+def test_detect_gender():
+    assert petrovich.detect_gender("Петрович") == Gender.MALE
+    assert petrovich.detect_gender("ПЕТРОВНА") == Gender.FEMALE
+    assert petrovich.detect_gender("Оглы") == Gender.ANDROGYNOUS
+
+
+def test_enums():
+    assert Case.DATIVE == Case.DATIVE
+    assert Case.DATIVE != Case.GENITIVE
+    assert len({Gender.MALE, Gender.FEMALE, Gender.ANDROGYNOUS}) == 3
+
+
+def test_rules_errors():
+    with pytest.raises(ValueError):
+        Petrovich.from_yaml("lastname: [")
+    with pytest.raises(ValueError):
+        Petrovich("missing/rules.yml")
@@ -0,0 +1,273 @@
+use axum::extract::rejection::JsonRejection;
+use axum::extract::{DefaultBodyLimit, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use petrovich_core::case::Case;
+use petrovich_core::gender::Gender;
+use petrovich_core::name::{FullName, NamePart};
+use petrovich_core::Petrovich;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+const CASES: [Case; 6] = [
+    Case::Nominative,
+    Case::Genitive,
+    Case::Dative,
+    Case::Accusative,
+    Case::Instrumental,
+    Case::Prepositional,
+];
+
+#[derive(Debug, Clone)]
+pub struct Limits {
+    //size of a request body in bytes
+    pub body_bytes: usize,
+    //number of names in a batch request
+    pub batch_items: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            body_bytes: 64 * 1024,
+            batch_items: 1000,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct AppState {
+    petrovich: Arc<Petrovich>,
+    limits: Limits,
+}
+
+//Parameters are strings as in the rules file: "lastname", "male", "genitive"
+#[derive(Deserialize)]
+pub struct InflectRequest {
+    pub part: String,
+    pub name: String,
+    pub gender: String,
+    pub case: String,
+}
+
+#[derive(Serialize)]
+pub struct InflectResponse {
+    pub result: String,
+}
+
+//Without `gender` it is detected by the middle name
+#[derive(Deserialize)]
+pub struct FullNameRequest {
+    pub last_name: Option<String>,
+    pub first_name: Option<String>,
+    pub middle_name: Option<String>,
+    pub gender: Option<String>,
+    pub case: String,
+}
+
+#[derive(Deserialize)]
+pub struct DeclensionRequest {
+    pub last_name: Option<String>,
+    pub first_name: Option<String>,
+    pub middle_name: Option<String>,
+    pub gender: Option<String>,
+}
+
+#[derive(Serialize, PartialEq, Debug)]
+pub struct FullNameResponse {
+    pub last_name: Option<String>,
+    pub first_name: Option<String>,
+    pub middle_name: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct BatchRequest {
+    pub requests: Vec<InflectRequest>,
+}
+
+//A failed item does not fail the whole batch
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum BatchItem {
+    Ok { result: String },
+    Err { error: String },
+}
+
+#[derive(Serialize)]
+pub struct BatchResponse {
+    pub results: Vec<BatchItem>,
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+pub struct ApiError {
+    status: StatusCode,
+    message: String,
+}
+
+impl ApiError {
+    fn bad_request(message: String) -> ApiError {
+        ApiError {
+            status: StatusCode::BAD_REQUEST,
+            message,
+        }
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        ApiError {
+            status: rejection.status(),
+            message: rejection.body_text(),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = ErrorResponse {
+            error: self.message,
+        };
+        (self.status, Json(body)).into_response()
+    }
+}
+
+type ApiResult<T> = Result<Json<T>, ApiError>;
+
+pub fn router(petrovich: Arc<Petrovich>, limits: Limits) -> Router {
+    let body_bytes = limits.body_bytes;
+    Router::new()
+        .route("/health", get(health))
+        .route("/inflect", post(inflect))
+        .route("/full-name", post(full_name))
+        .route("/declension", post(declension))
+        .route("/batch", post(batch))
+        .layer(DefaultBodyLimit::max(body_bytes))
+        .with_state(AppState { petrovich, limits })
+}
+
+async fn health() -> Json<BTreeMap<&'static str, &'static str>> {
+    Json(BTreeMap::from([("status", "ok")]))
+}
+
+async fn inflect(
+    State(state): State<AppState>,
+    request: Result<Json<InflectRequest>, JsonRejection>,
+) -> ApiResult<InflectResponse> {
+    let Json(request) = request?;
+    let result = inflect_one(&state.petrovich, &request).map_err(ApiError::bad_request)?;
+    Ok(Json(InflectResponse { result }))
+}
+
+async fn full_name(
+    State(state): State<AppState>,
+    request: Result<Json<FullNameRequest>, JsonRejection>,
+) -> ApiResult<FullNameResponse> {
+    let Json(request) = request?;
+    let name = to_full_name(
+        &request.last_name,
+        &request.first_name,
+        &request.middle_name,
+    );
+    let gender = to_name_gender(&request.gender, &name)?;
+    let case = to_case(&request.case).map_err(ApiError::bad_request)?;
+    let result = state.petrovich.full_name(&name, &gender, &case);
+    Ok(Json(to_response(result)))
+}
+
+//All six cases of a full name
+async fn declension(
+    State(state): State<AppState>,
+    request: Result<Json<DeclensionRequest>, JsonRejection>,
+) -> ApiResult<BTreeMap<&'static str, FullNameResponse>> {
+    let Json(request) = request?;
+    let name = to_full_name(
+        &request.last_name,
+        &request.first_name,
+        &request.middle_name,
+    );
+    let gender = to_name_gender(&request.gender, &name)?;
+    let result = CASES
+        .iter()
+        .map(|case| {
+            let inflected = state.petrovich.full_name(&name, &gender, case);
+            (case.value(), to_response(inflected))
+        })
+        .collect();
+    Ok(Json(result))
+}
+
+async fn batch(
+    State(state): State<AppState>,
+    request: Result<Json<BatchRequest>, JsonRejection>,
+) -> ApiResult<BatchResponse> {
+    let Json(request) = request?;
+    if request.requests.len() > state.limits.batch_items {
+        return Err(ApiError {
+            status: StatusCode::PAYLOAD_TOO_LARGE,
+            message: format!(
+                "Batch of {} names exceeds the limit of {}",
+                request.requests.len(),
+                state.limits.batch_items
+            ),
+        });
+    }
+    let results = request
+        .requests
+        .iter()
+        .map(|item| match inflect_one(&state.petrovich, item) {
+            Ok(result) => BatchItem::Ok { result },
+            Err(error) => BatchItem::Err { error },
+        })
+        .collect();
+    Ok(Json(BatchResponse { results }))
+}
+
+fn inflect_one(petrovich: &Petrovich, request: &InflectRequest) -> Result<String, String> {
+    let part = NamePart::of(&request.part)
+        .ok_or_else(|| format!("Unknown name part '{}'", request.part))?;
+    let gender = to_gender(&request.gender)?;
+    let case = to_case(&request.case)?;
+    Ok(petrovich.inflect(&part, &request.name, &gender, &case))
+}
+
+fn to_full_name(
+    last_name: &Option<String>,
+    first_name: &Option<String>,
+    middle_name: &Option<String>,
+) -> FullName {
+    FullName::new(
+        last_name.as_deref().unwrap_or_default(),
+        first_name.as_deref().unwrap_or_default(),
+        middle_name.as_deref().unwrap_or_default(),
+    )
+}
+
+fn to_response(name: FullName) -> FullNameResponse {
+    FullNameResponse {
+        last_name: name.last_name,
+        first_name: name.first_name,
+        middle_name: name.middle_name,
+    }
+}
+
+fn to_name_gender(gender: &Option<String>, name: &FullName) -> Result<Gender, ApiError> {
+    match gender {
+        Some(gender) => to_gender(gender).map_err(ApiError::bad_request),
+        None => Ok(name.gender()),
+    }
+}
+
+fn to_gender(gender: &str) -> Result<Gender, String> {
+    Gender::of(gender).ok_or_else(|| format!("Unknown gender '{}'", gender))
+}
+
+fn to_case(case: &str) -> Result<Case, String> {
+    Case::of(case).ok_or_else(|| format!("Unknown case '{}'", case))
+}
@@ -0,0 +1,64 @@
+use clap::Parser;
+use petrovich_core::Petrovich;
+use petrovich_server::Limits;
+use std::error::Error;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+#[derive(Parser)]
+#[command(about = "HTTP/JSON server inflecting Russian names")]
+struct Args {
+    #[arg(
+        long,
+        default_value = "127.0.0.1:8080",
+        help = "Address to listen on, only local connections by default"
+    )]
+    addr: SocketAddr,
+    #[arg(
+        long,
+        help = "Rules file, the embedded rules are used if the server is built with them"
+    )]
+    rules: Option<String>,
+    #[arg(
+        long,
+        default_value_t = Limits::default().body_bytes,
+        help = "Maximal size of a request body in bytes"
+    )]
+    max_body_bytes: usize,
+    #[arg(
+        long,
+        default_value_t = Limits::default().batch_items,
+        help = "Maximal number of names in a batch request"
+    )]
+    max_batch_items: usize,
+}
+
+fn load_petrovich(rules: Option<&str>) -> Result<Petrovich, Box<dyn Error>> {
+    match rules {
+        Some(rules) => Petrovich::new(rules),
+        #[cfg(feature = "embedded-rules")]
+        None => Petrovich::embedded(),
+        #[cfg(not(feature = "embedded-rules"))]
+        None => Err("No rules file given, use --rules".into()),
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    let petrovich = load_petrovich(args.rules.as_deref())?;
+    let limits = Limits {
+        body_bytes: args.max_body_bytes,
+        batch_items: args.max_batch_items,
+    };
+    let router = petrovich_server::router(Arc::new(petrovich), limits);
+    let listener = TcpListener::bind(args.addr).await?;
+    println!("Listening on http://{}", listener.local_addr()?);
+    axum::serve(listener, router)
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
+    Ok(())
+}
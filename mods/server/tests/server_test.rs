@@ -0,0 +1,162 @@
+use petrovich_core::Petrovich;
+use petrovich_server::Limits;
+use reqwest::{Client, StatusCode};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+const RULES: &str = "
+firstname:
+  suffixes:
+    - gender: male
+      test: [н]
+      mods: [а, у, а, ом, е]
+lastname:
+  suffixes:
+    - gender: male
+      test: [ов]
+      mods: [а, у, а, ым, е]
+middlename:
+  suffixes:
+    - gender: male
+      test: [ич]
+      mods: [а, у, а, ем, е]
+";
+
+struct TestServer {
+    url: String,
+    client: Client,
+}
+
+impl TestServer {
+    async fn start(limits: Limits) -> TestServer {
+        let petrovich: Petrovich = RULES.parse().unwrap();
+        let router = petrovich_server::router(Arc::new(petrovich), limits);
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        TestServer {
+            url,
+            client: Client::new(),
+        }
+    }
+
+    async fn post(&self, path: &str, body: &Value) -> (StatusCode, Value) {
+        let response = self
+            .client
+            .post(format!("{}{}", self.url, path))
+            .json(body)
+            .send()
+            .await
+            .unwrap();
+        (response.status(), response.json().await.unwrap())
+    }
+}
+
+#[tokio::test]
+async fn health_test() {
+    let server = TestServer::start(Limits::default()).await;
+    let response = reqwest::get(format!("{}/health", server.url))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!(
+        json!({"status": "ok"}),
+        response.json::<Value>().await.unwrap()
+    );
+}
+
+#[tokio::test]
+async fn inflect_test() {
+    let server = TestServer::start(Limits::default()).await;
+    let request = json!({"part": "lastname", "name": "Иванов", "gender": "male", "case": "dative"});
+    let actual = server.post("/inflect", &request).await;
+    assert_eq!((StatusCode::OK, json!({"result": "иванову"})), actual);
+
+    let request = json!({"part": "lastname", "name": "Иванов", "gender": "male", "case": "datve"});
+    let actual = server.post("/inflect", &request).await;
+    assert_eq!(
+        (
+            StatusCode::BAD_REQUEST,
+            json!({"error": "Unknown case 'datve'"})
+        ),
+        actual
+    );
+}
+
+#[tokio::test]
+async fn full_name_test() {
+    let server = TestServer::start(Limits::default()).await;
+    let request = json!({
+        "last_name": "Иванов",
+        "first_name": "Иван",
+        "middle_name": "Иванович",
+        "case": "instrumental"
+    });
+    let expected = json!({
+        "last_name": "Ивановым",
+        "first_name": "Иваном",
+        "middle_name": "Ивановичем"
+    });
+    assert_eq!(
+        (StatusCode::OK, expected),
+        server.post("/full-name", &request).await
+    );
+}
+
+#[tokio::test]
+async fn declension_test() {
+    let server = TestServer::start(Limits::default()).await;
+    let request = json!({"last_name": "Иванов", "gender": "male"});
+    let (status, actual) = server.post("/declension", &request).await;
+    assert_eq!(StatusCode::OK, status);
+    assert_eq!(6, actual.as_object().unwrap().len());
+    assert_eq!(json!("Иванов"), actual["nominative"]["last_name"]);
+    assert_eq!(json!("Иванова"), actual["genitive"]["last_name"]);
+    assert_eq!(json!("Иванове"), actual["prepositional"]["last_name"]);
+    assert_eq!(Value::Null, actual["genitive"]["first_name"]);
+}
+
+#[tokio::test]
+async fn batch_test() {
+    let limits = Limits {
+        batch_items: 2,
+        ..Limits::default()
+    };
+    let server = TestServer::start(limits).await;
+    let request = json!({"requests": [
+        {"part": "firstname", "name": "Иван", "gender": "male", "case": "genitive"},
+        {"part": "surname", "name": "Иванов", "gender": "male", "case": "genitive"}
+    ]});
+    let expected = json!({"results": [
+        {"result": "ивана"},
+        {"error": "Unknown name part 'surname'"}
+    ]});
+    assert_eq!(
+        (StatusCode::OK, expected),
+        server.post("/batch", &request).await
+    );
+
+    let item = json!({"part": "firstname", "name": "Иван", "gender": "male", "case": "genitive"});
+    let request = json!({"requests": [item, item, item]});
+    let expected = json!({"error": "Batch of 3 names exceeds the limit of 2"});
+    let actual = server.post("/batch", &request).await;
+    assert_eq!((StatusCode::PAYLOAD_TOO_LARGE, expected), actual);
+}
+
+#[tokio::test]
+async fn limits_test() {
+    let limits = Limits {
+        body_bytes: 100,
+        ..Limits::default()
+    };
+    let server = TestServer::start(limits).await;
+    let name = "Иванов".repeat(20);
+    let request = json!({"part": "lastname", "name": name, "gender": "male", "case": "dative"});
+    let (status, _) = server.post("/inflect", &request).await;
+    assert_eq!(StatusCode::PAYLOAD_TOO_LARGE, status);
+
+    let (status, actual) = server.post("/inflect", &json!({"name": "Иванов"})).await;
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, status);
+    assert!(actual["error"].as_str().unwrap().contains("missing field"));
+}
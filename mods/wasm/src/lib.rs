@@ -0,0 +1,129 @@
+use petrovich_core::case::Case;
+use petrovich_core::gender::Gender;
+use petrovich_core::name::NamePart;
+use wasm_bindgen::prelude::*;
+
+//Parameters are strings as in the rules file:
+//"male", "female", "androgynous"; "genitive", "dative"...; "lastname", "firstname", "middlename"
+#[wasm_bindgen]
+pub struct Petrovich {
+    inner: petrovich_core::Petrovich,
+}
+
+#[wasm_bindgen(getter_with_clone)]
+pub struct FullName {
+    #[wasm_bindgen(js_name = lastName)]
+    pub last_name: Option<String>,
+    #[wasm_bindgen(js_name = firstName)]
+    pub first_name: Option<String>,
+    #[wasm_bindgen(js_name = middleName)]
+    pub middle_name: Option<String>,
+}
+
+#[wasm_bindgen]
+impl Petrovich {
+    //Rules compiled into the module, available with the `embedded-rules` feature
+    #[cfg(feature = "embedded-rules")]
+    pub fn embedded() -> Result<Petrovich, JsError> {
+        let inner = petrovich_core::Petrovich::embedded().map_err(to_js_error)?;
+        Ok(Petrovich { inner })
+    }
+
+    //Loads rules from a YAML string
+    #[wasm_bindgen(js_name = fromYaml)]
+    pub fn from_yaml(content: &str) -> Result<Petrovich, JsError> {
+        let inner = content.parse().map_err(to_js_error)?;
+        Ok(Petrovich { inner })
+    }
+
+    pub fn inflect(
+        &self,
+        part: &str,
+        name: &str,
+        gender: &str,
+        case: &str,
+    ) -> Result<String, JsError> {
+        let part = NamePart::of(part).ok_or_else(|| unknown("name part", part))?;
+        Ok(self
+            .inner
+            .inflect(&part, name, &to_gender(gender)?, &to_case(case)?))
+    }
+
+    #[wasm_bindgen(js_name = firstName)]
+    pub fn first_name(&self, name: &str, gender: &str, case: &str) -> Result<String, JsError> {
+        self.inflect(NamePart::FirstName.value(), name, gender, case)
+    }
+
+    #[wasm_bindgen(js_name = lastName)]
+    pub fn last_name(&self, name: &str, gender: &str, case: &str) -> Result<String, JsError> {
+        self.inflect(NamePart::LastName.value(), name, gender, case)
+    }
+
+    #[wasm_bindgen(js_name = middleName)]
+    pub fn middle_name(&self, name: &str, gender: &str, case: &str) -> Result<String, JsError> {
+        self.inflect(NamePart::MiddleName.value(), name, gender, case)
+    }
+
+    //Keeps the letter case of the name.
+    //Without `gender` it is detected by the middle name.
+    #[wasm_bindgen(js_name = fullName)]
+    pub fn full_name(
+        &self,
+        last_name: Option<String>,
+        first_name: Option<String>,
+        middle_name: Option<String>,
+        case: &str,
+        gender: Option<String>,
+    ) -> Result<FullName, JsError> {
+        let name = petrovich_core::name::FullName::new(
+            last_name.as_deref().unwrap_or_default(),
+            first_name.as_deref().unwrap_or_default(),
+            middle_name.as_deref().unwrap_or_default(),
+        );
+        let gender = match gender {
+            Some(gender) => to_gender(&gender)?,
+            None => name.gender(),
+        };
+        let result = self.inner.full_name(&name, &gender, &to_case(case)?);
+        Ok(FullName {
+            last_name: result.last_name,
+            first_name: result.first_name,
+            middle_name: result.middle_name,
+        })
+    }
+}
+
+#[wasm_bindgen]
+impl FullName {
+    //Present parts joined with spaces
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_js_string(&self) -> String {
+        [&self.last_name, &self.first_name, &self.middle_name]
+            .into_iter()
+            .filter_map(|part| part.as_deref())
+            .collect::<Vec<&str>>()
+            .join(" ")
+    }
+}
+
+//Gender by the middle name
+#[wasm_bindgen(js_name = detectGender)]
+pub fn detect_gender(middle_name: &str) -> String {
+    Gender::detect_gender(middle_name).value().to_string()
+}
+
+fn to_gender(gender: &str) -> Result<Gender, JsError> {
+    Gender::of(gender).ok_or_else(|| unknown("gender", gender))
+}
+
+fn to_case(case: &str) -> Result<Case, JsError> {
+    Case::of(case).ok_or_else(|| unknown("case", case))
+}
+
+fn unknown(name: &str, value: &str) -> JsError {
+    JsError::new(&format!("Unknown {} '{}'", name, value))
+}
+
+fn to_js_error(error: Box<dyn std::error::Error>) -> JsError {
+    JsError::new(&error.to_string())
+}
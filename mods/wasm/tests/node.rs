@@ -0,0 +1,82 @@
+// Run with `wasm-pack test --node`
+#![cfg(target_arch = "wasm32")]
+
+use petrovich_wasm::{detect_gender, Petrovich};
+use wasm_bindgen_test::*;
+
+const RULES: &str = "
+firstname:
+  suffixes:
+    - gender: male
+      test: [н]
+      mods: [а, у, а, ом, е]
+lastname:
+  suffixes:
+    - gender: male
+      test: [ов]
+      mods: [а, у, а, ым, е]
+middlename:
+  suffixes:
+    - gender: male
+      test: [ич]
+      mods: [а, у, а, ем, е]
+";
+
+fn petrovich() -> Petrovich {
+    Petrovich::from_yaml(RULES).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn inflect_test() {
+    let petrovich = petrovich();
+    let actual = petrovich.inflect("lastname", "Иванов", "male", "dative");
+    assert_eq!("иванову", actual.unwrap());
+    let actual = petrovich.first_name("Иван", "male", "instrumental");
+    assert_eq!("иваном", actual.unwrap());
+    let actual = petrovich.middle_name("Иванович", "male", "genitive");
+    assert_eq!("ивановича", actual.unwrap());
+}
+
+#[wasm_bindgen_test]
+fn full_name_test() {
+    let petrovich = petrovich();
+    let actual = petrovich
+        .full_name(
+            Some("Иванов".to_string()),
+            Some("Иван".to_string()),
+            Some("Иванович".to_string()),
+            "dative",
+            None,
+        )
+        .unwrap();
+    assert_eq!(Some("Иванову".to_string()), actual.last_name);
+    assert_eq!("Иванову Ивану Ивановичу", actual.to_js_string());
+    let actual = petrovich
+        .full_name(
+            None,
+            Some("Иван".to_string()),
+            None,
+            "genitive",
+            Some("male".to_string()),
+        )
+        .unwrap();
+    assert_eq!("Ивана", actual.to_js_string());
+}
+
+#[wasm_bindgen_test]
+fn errors_test() {
+    let petrovich = petrovich();
+    assert!(petrovich
+        .inflect("surname", "Иванов", "male", "dative")
+        .is_err());
+    assert!(petrovich.last_name("Иванов", "man", "dative").is_err());
+    assert!(petrovich.last_name("Иванов", "male", "datve").is_err());
+    assert!(Petrovich::from_yaml("lastname: [").is_err());
+}
+
+#[wasm_bindgen_test]
+fn detect_gender_test() {
+    assert_eq!("male", detect_gender("Иванович"));
+    assert_eq!("female", detect_gender("ИВАНОВНА"));
+    assert_eq!("androgynous", detect_gender(""));
+}